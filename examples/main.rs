@@ -37,9 +37,10 @@ fn main() {
                     p
                 );
             }
-            _ => {
+            reward => {
                 println!(
-                    "--------------------------------------------DRAW---------------------------------------------- \n\n\n\n"
+                    "--------------------------------------------DRAW ({:?})---------------------------------------------- \n\n\n\n",
+                    reward
                 );
             }
         }
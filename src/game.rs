@@ -1,3 +1,4 @@
 pub(crate) mod board;
 pub(crate) mod model;
+pub(crate) mod pdn;
 pub(crate) mod utils;
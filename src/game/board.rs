@@ -1,3 +1,9 @@
 pub(crate) mod bitboard;
+pub mod international;
+pub(crate) mod perft;
+pub(crate) mod render;
+pub mod rules;
 pub mod scale;
 pub mod state;
+pub(crate) mod svg;
+pub(crate) mod zobrist;
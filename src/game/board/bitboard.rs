@@ -1,16 +1,33 @@
 use crate::Scale::*;
+use crate::game::board::rules::{Capture, KingRange, Majority, Promotion, RuleSet};
 use crate::game::model::bits::Bits;
 use crate::game::model::player::Player;
 use crate::game::model::sq::Sq;
-use crate::game::model::{action::Action, path::ActionPath};
+use crate::game::model::{action::Action, movelist::MoveList, path::ActionPath};
 
 pub(crate) struct BitBoard {
     pub(super) current: u32,
     pub(super) team: u32,
     pub(super) other: u32,
     pub(super) kings: u32,
+    pub(super) rules: RuleSet,
 }
 
+/// A man or a short range king in the middle of its jump sequence, and the board it sees
+#[derive(Clone, Copy)]
+struct Jumper {
+    king: bool,
+    /// The squares it can not land on, including the pieces it already captured
+    occupied: u32,
+    /// The opponent's pieces it can still capture
+    other: u32,
+    turn: Player,
+}
+
+/// The (mask, offset) pairs that walk one square along a diagonal of the 32bits bitboard,
+/// a square in the first mask moves by the first offset, and a square in the second mask by the second offset
+type Direction = [(u32, i8); 2];
+
 impl BitBoard {
     const NORTH_LEFT_3: u32 = 0xE0E0E0E; // +3
     const NORTH_LEFT_4: u32 = 0xF0F0F0; // + 4
@@ -24,156 +41,646 @@ impl BitBoard {
     const SOUTH_RIGHT_4: u32 = 0xF0F0F0F; // + 4
     const SOUTH_RIGHT_3: u32 = 0x70707070; // + 3
 
-    fn try_move(
+    const NORTH_EAST: Direction = [(Self::NORTH_RIGHT_4, 4), (Self::NORTH_RIGHT_5, 5)];
+    const NORTH_WEST: Direction = [(Self::NORTH_LEFT_4, 4), (Self::NORTH_LEFT_3, 3)];
+    const SOUTH_EAST: Direction = [(Self::SOUTH_RIGHT_3, -3), (Self::SOUTH_RIGHT_4, -4)];
+    const SOUTH_WEST: Direction = [(Self::SOUTH_LEFT_4, -4), (Self::SOUTH_LEFT_5, -5)];
+
+    const DIRECTIONS: [Direction; 4] = [
+        Self::NORTH_EAST,
+        Self::NORTH_WEST,
+        Self::SOUTH_EAST,
+        Self::SOUTH_WEST,
+    ];
+
+    /// The square next to every square along each diagonal (in the order of `DIRECTIONS`), or 32 past the edge of
+    /// the board. A piece carrying on its jump sequence walks these, instead of the masks of the whole board
+    const NEIGHBOURS: [[u8; 32]; 4] = Self::neighbours();
+
+    const fn neighbours() -> [[u8; 32]; 4] {
+        let mut neighbours = [[32; 32]; 4];
+        let mut direction = 0;
+
+        while direction < Self::DIRECTIONS.len() {
+            let [(maska, shfta), (maskb, shftb)] = Self::DIRECTIONS[direction];
+            let mut sq = 0;
+
+            while sq < 32 {
+                let bit = 1 << sq;
+                let offset =
+                    (shfta * ((bit & maska) != 0) as i8) + (shftb * ((bit & maskb) != 0) as i8);
+                let next = sq as i8 + offset;
+
+                if offset != 0 && 0 <= next && next < 32 {
+                    neighbours[direction][sq] = next as u8;
+                }
+                sq += 1;
+            }
+            direction += 1;
+        }
+
+        neighbours
+    }
+
+    /// Returns the next square after `sq` along the diagonal (direction),
+    /// or None if `sq` is already on the edge of the board in that direction
+    fn step(sq: u8, [(maska, shfta), (maskb, shftb)]: Direction) -> Option<u8> {
+        let bit = 1 << sq;
+        let offset = (shfta * ((bit & maska) != 0) as i8) + (shftb * ((bit & maskb) != 0) as i8);
+
+        if offset == 0 {
+            return None;
+        }
+
+        Sq::try_from((sq, offset)).map(u8::from).ok()
+    }
+
+    /// Moves every square of `bits` by the offset, a positive offset moves them north and a negative one south.
+    /// The squares moved outside of the board are dropped
+    fn offset(bits: u32, by: i8) -> u32 {
+        match by > 0 {
+            true => bits << by,
+            false => bits >> -by,
+        }
+    }
+
+    /// The forward and the backward diagonals of the player
+    fn diagonals(turn: Player) -> ([Direction; 2], [Direction; 2]) {
+        match turn {
+            Player::North => (
+                [Self::SOUTH_EAST, Self::SOUTH_WEST],
+                [Self::NORTH_EAST, Self::NORTH_WEST],
+            ),
+            Player::South => (
+                [Self::NORTH_EAST, Self::NORTH_WEST],
+                [Self::SOUTH_EAST, Self::SOUTH_WEST],
+            ),
+        }
+    }
+
+    /// The pieces on `current`: (men, short range kings, flying kings)
+    fn pieces(&self) -> (u32, u32, u32) {
+        let men = self.current & !self.kings;
+
+        match self.rules.king_range {
+            KingRange::Short => (men, self.current & self.kings, 0),
+            KingRange::Flying => (men, 0, self.current & self.kings),
+        }
+    }
+
+    /// Visits the quiet moves of all the `pieces` along the diagonal (direction) at once: every piece is moved
+    /// by the offset of its half of the board, and the moves landing on an empty square are kept.
+    /// The pieces landing on `base` are flagged as promoted
+    fn slide<F: FnMut(ActionPath)>(
+        &self,
+        pieces: u32,
+        direction: Direction,
+        base: u32,
+        visit: &mut F,
+    ) {
+        let empty = !(self.current | self.other | self.team);
+
+        for (mask, by) in direction {
+            for tgt in Bits::from(Self::offset(pieces & mask, by) & empty) {
+                let promoted = ((1 << tgt) & base) != 0;
+                let src = (tgt as i8 - by) as u8;
+                visit(ActionPath::from(Action::new_32(src, tgt, false, promoted)));
+            }
+        }
+    }
+
+    /// The squares all the `pieces` land on by jumping along the diagonal (direction) at once, with the offsets
+    /// leading back to the captured piece and then to the jumping one. The squares next to the pieces holding
+    /// an opponent's piece are found first, and the landing squares are the empty squares right behind them
+    fn landings(&self, pieces: u32, direction: Direction) -> [(u32, i8, i8); 2] {
+        let empty = !(self.current | self.other | self.team);
+        let [(a_mask, a_by), (b_mask, b_by)] = direction;
+
+        // a piece and the square behind it are on rows of different parities, hence on both halves of the direction
+        let a_mids = Self::offset(pieces & a_mask, a_by) & self.other;
+        let b_mids = Self::offset(pieces & b_mask, b_by) & self.other;
+        [
+            (Self::offset(a_mids & b_mask, b_by) & empty, b_by, a_by),
+            (Self::offset(b_mids & a_mask, a_by) & empty, a_by, b_by),
+        ]
+    }
+
+    /// Visits the jump sequences of all the `pieces` starting along the diagonal (direction): their first jumps
+    /// are found at once, then every piece carries on alone. The pieces landing on `base` are flagged as promoted
+    fn jump<F: FnMut(ActionPath)>(
         &self,
-        src: u8,
         turn: Player,
-    ) -> impl Fn((u32, i8), (u32, i8)) -> Option<(Action, u8)> {
-        let curr = 1 << src;
-        let empty = !(curr | self.current | self.other | self.team);
+        pieces: u32,
+        direction: Direction,
+        base: u32,
+        visit: &mut F,
+    ) {
+        for (lands, mid_by, src_by) in self.landings(pieces, direction) {
+            for tgt in Bits::from(lands) {
+                let mid = (tgt as i8 - mid_by) as u8;
+                let src = (mid as i8 - src_by) as u8;
+                let promoted = ((1 << tgt) & base) != 0;
+
+                let piece = Jumper {
+                    king: ((1 << src) & self.kings) != 0,
+                    occupied: self.current | self.other | self.team,
+                    other: self.other,
+                    turn,
+                };
+                let action = Action::new_32(src, tgt, true, promoted);
+                self.hop(piece, action, mid, &ActionPath::new(U32), visit);
+            }
+        }
+    }
+
+    /// Visits every jump sequence of the (man or short range king) `piece` starting with its jump `action` over `mid`,
+    /// each of them following the jumps of `prefix`
+    fn hop<F: FnMut(ActionPath)>(
+        &self,
+        piece: Jumper,
+        action: Action,
+        mid: u8,
+        prefix: &ActionPath,
+        visit: &mut F,
+    ) {
+        let Action {
+            src, tgt, promoted, ..
+        } = action;
+
+        let (crowned, jumps) = self.crowning(promoted, piece.king);
 
-        move |(maska, shfta): (u32, i8), (maskb, shftb): (u32, i8)| -> Option<(Action, u8)> {
-            let offset =
-                (shfta * ((curr & maska) != 0) as i8) + (shftb * ((curr & maskb) != 0) as i8);
+        // whether this jump continues into (atleast) one longer jump sequence
+        let mut continues = false;
 
-            let Ok(mid) = Sq::try_from((src, offset)).map(u8::from) else {
-                return None;
+        if jumps {
+            let mut path = *prefix;
+            path.append(Action {
+                promoted: crowned,
+                ..action
+            })
+            .unwrap();
+
+            let (occupied, other) = Self::jumped(piece.occupied, piece.other, src, tgt, mid);
+            let next = Jumper {
+                king: piece.king || crowned,
+                occupied,
+                other,
+                turn: piece.turn,
             };
-            let mid_bit = 1 << mid;
-            let promoted = (mid_bit & turn.opponent_base()) != 0;
-
-            if (mid_bit & empty) != 0 {
-                // actions.push((Action::new_32(src, mid, false, promoted), 0));
-                return Some((Action::new_32(src, mid, false, promoted), 0));
-            } else if (mid_bit & self.other) != 0 {
-                let second_offset = (shfta * ((mid_bit & maska) != 0) as i8)
-                    + (shftb * ((mid_bit & maskb) != 0) as i8);
-
-                let Ok(tgt) = Sq::try_from((mid, second_offset)).map(u8::from) else {
-                    return None;
-                };
-                let tgt_bit = 1 << tgt;
-                let promoted = (tgt_bit & turn.opponent_base()) != 0;
-                if (tgt_bit & empty) != 0 {
-                    // actions.push((Action::new_32(src, tgt, true, promoted), mid));\
-                    return Some((Action::new_32(src, tgt, true, promoted), mid));
+
+            continues = match next.king && !piece.king && self.rules.king_range == KingRange::Flying
+            {
+                // a man crowned in the middle of its jump sequence carries on as a flying king
+                true => {
+                    let team = next.occupied & !next.other & !(1 << tgt);
+                    let board = BitBoard::new(1 << tgt, next.other, team, 1 << tgt, self.rules);
+
+                    let mut flies = false;
+                    board.paths(piece.turn, &path, true, &mut |path| {
+                        flies = true;
+                        visit(path);
+                    });
+                    flies
                 }
+                false => self.hops(next, tgt, &path, visit),
+            };
+        }
+
+        self.finish(continues, prefix, action, visit);
+    }
+
+    /// Visits the jump sequences of the (man or short range king) `piece` on `sq`, each of them following the jumps
+    /// of `prefix`, and returns whether it can jump again at all
+    fn hops<F: FnMut(ActionPath)>(
+        &self,
+        piece: Jumper,
+        sq: u8,
+        prefix: &ActionPath,
+        visit: &mut F,
+    ) -> bool {
+        // the bases the piece is promoted on, as in `moves`, and whether it can jump backwards at all
+        let forward = Some(piece.turn.opponent_base());
+        let backward = match piece.king {
+            true => Some((!piece.turn).opponent_base()),
+            false => self.rules.men_capture_backwards.then_some(0),
+        };
+
+        // the diagonals of the piece (as indices of `DIRECTIONS`), with the base it is promoted on along them
+        let directions = match piece.turn {
+            Player::North => [(2, forward), (3, forward), (0, backward), (1, backward)],
+            Player::South => [(0, forward), (1, forward), (2, backward), (3, backward)],
+        };
+        let mut jumps = false;
+
+        for (direction, base) in directions {
+            let Some(base) = base else {
+                continue;
+            };
+
+            let mid = Self::NEIGHBOURS[direction][sq as usize];
+            if mid >= 32 || ((1 << mid) & piece.other) == 0 {
+                continue;
+            }
+
+            let tgt = Self::NEIGHBOURS[direction][mid as usize];
+            if tgt >= 32 || ((1 << tgt) & piece.occupied) != 0 {
+                continue;
             }
 
-            None
+            jumps = true;
+            let promoted = ((1 << tgt) & base) != 0;
+            self.hop(
+                piece,
+                Action::new_32(sq, tgt, true, promoted),
+                mid,
+                prefix,
+                visit,
+            );
         }
+
+        jumps
     }
 
-    /// When calling this function, always ensure that only one bit (i.e the main moving piece) is set in self.current
-    /// All other pieces can be part of `team` or `others`
-    /// returns: (src, tgt, capture)
-    /// if capture is 0, it means there is no piece for this move to capture
-    fn shift(&self, src: u8, turn: Player) -> Vec<(Action, u8)> {
-        let mut actions: Vec<(Action, u8)> = vec![];
+    /// Moves of a flying king on `src`: the king slides any number of empty squares along each diagonal,
+    /// and captures the first opponent's piece it meets on a diagonal by landing on any of the empty squares behind it
+    /// visits: (src, tgt, capture) and the captured piece (0 if it does not capture)
+    fn fly(&self, src: u8, visit: &mut dyn FnMut(Action, u8)) {
+        let empty = !(self.current | self.other | self.team);
 
-        let mv = self.try_move(src, turn);
-        let mut push = |res: Option<(Action, u8)>| res.map(|action| actions.push(action));
+        for direction in Self::DIRECTIONS {
+            let mut sq = src;
 
-        match turn {
-            Player::North => {
-                // explores moves towards the south (as a nothern player)
-                push(mv((Self::SOUTH_RIGHT_3, -3), (Self::SOUTH_RIGHT_4, -4))); // south-east
-                push(mv((Self::SOUTH_LEFT_4, -4), (Self::SOUTH_LEFT_5, -5))); // south-west
+            while let Some(next) = Self::step(sq, direction) {
+                let bit = 1 << next;
+
+                if (bit & empty) != 0 {
+                    visit(Action::new_32(src, next, false, false), 0);
+                    sq = next;
+                    continue;
+                }
+
+                if (bit & self.other) != 0 {
+                    let mut land = next;
+
+                    while let Some(tgt) = Self::step(land, direction) {
+                        if ((1 << tgt) & empty) == 0 {
+                            break;
+                        }
+
+                        visit(Action::new_32(src, tgt, true, false), next);
+                        land = tgt;
+                    }
+                }
+
+                break;
+            }
+        }
+    }
+
+    /// Visits every first step (a quiet move, or a single jump) of the flying kings on `current`, alongside the piece
+    /// it captures (0 if it does not capture). The flying kings slide square by square, while the men and the short
+    /// range kings are moved all at once by `moves`
+    fn steps(&self, captures_only: bool, visit: &mut dyn FnMut(Action, u8)) {
+        let (_, _, flying) = self.pieces();
+
+        for src in Bits::from(flying) {
+            self.fly(src, &mut |action, captured| {
+                if action.capture || !captures_only {
+                    visit(action, captured);
+                }
+            });
+        }
+    }
+
+    /// Whether any of the pieces on `current` can capture atleast one of the opponent's pieces.
+    /// The jumps of the men and the short range kings are looked up on the masks of the whole board
+    fn can_jump(&self, turn: Player) -> bool {
+        let (forward, backward) = Self::diagonals(turn);
+        let (men, short, flying) = self.pieces();
+        let back_men = men * u32::from(self.rules.men_capture_backwards);
+
+        let mut lands = 0;
+        for direction in forward {
+            for (landings, ..) in self.landings(men | short, direction) {
+                lands |= landings;
             }
-            Player::South => {
-                // explores moves towards the north (as a southern player)
-                push(mv((Self::NORTH_RIGHT_4, 4), (Self::NORTH_RIGHT_5, 5))); // north east
-                push(mv((Self::NORTH_LEFT_4, 4), (Self::NORTH_LEFT_3, 3))); // north east
+        }
+        for direction in backward {
+            for (landings, ..) in self.landings(short | back_men, direction) {
+                lands |= landings;
             }
         }
 
-        actions
+        if lands != 0 || flying == 0 {
+            return lands != 0;
+        }
+
+        let mut jumps = false;
+        self.steps(true, &mut |_, _| jumps = true);
+        jumps
     }
 
-    pub(crate) fn captured(&self, src: u8, tgt: u8) -> u8 {
-        let turn = Player::from(src < tgt);
+    /// Whether any of the pieces on `current` can move at all: either jump, or slide onto an empty square
+    /// next to it (the first square a flying king slides over is next to it too)
+    pub(crate) fn has_moves(&self, turn: Player) -> bool {
+        if self.can_jump(turn) {
+            return true;
+        }
 
-        self.shift(src, turn)
+        let empty = !(self.current | self.other | self.team);
+        let kings = self.current & self.kings;
+        let (forward, backward) = Self::diagonals(turn);
+
+        let slides = |pieces: u32, directions: [Direction; 2]| {
+            directions
+                .into_iter()
+                .flatten()
+                .any(|(mask, by)| (Self::offset(pieces & mask, by) & empty) != 0)
+        };
+
+        slides(self.current, forward) || slides(kings, backward)
+    }
+
+    /// Returns the opponent's piece captured by jumping from `src` to `tgt`.
+    /// The captured piece can be anywhere on the diagonal between `src` and `tgt` (e.g. with flying kings)
+    pub(crate) fn captured(&self, src: u8, tgt: u8) -> u8 {
+        Self::DIRECTIONS
             .into_iter()
-            .find(|(action, _)| action.src == src && action.tgt == tgt)
-            .map(|(_, cp)| cp)
+            .find_map(|direction| {
+                let mut sq = src;
+                let mut captured = None;
+
+                while let Some(next) = Self::step(sq, direction) {
+                    if next == tgt {
+                        return captured;
+                    }
+
+                    if ((1 << next) & self.other) != 0 {
+                        captured = Some(next);
+                    }
+
+                    sq = next;
+                }
+
+                None
+            })
             .unwrap_or(0)
     }
 
-    fn next<F>(&self, action: Action, captured: u8, turn: Player, mut func: F)
-    where
-        F: FnMut(ActionPath),
-    {
+    /// The board seen by the piece after its move (or jump) `action`: the board on which its jump sequence carries on,
+    /// whether the piece is crowned before its next jump, and whether it can jump again at all
+    fn after(&self, action: Action, captured: u8) -> (BitBoard, bool, bool) {
         let Action {
             src, tgt, promoted, ..
         } = action;
-        if tgt >= 32 {
-            return;
-        }
 
         let capture = captured != 0;
-        let parent = Action::new(src, tgt, capture, promoted, U32);
-
         let current = 1 << tgt;
-        let others = self.other & !(1 << captured);
-        let team = (self.team & !(1 << src)) | (self.current & !(1 << src)) | current;
+        let occupied = self.current | self.other | self.team;
+        let (occupied, others) = Self::jumped(occupied, self.other, src, tgt, captured);
+        let team = occupied & !others;
 
         // if moving piece is a king, remove from previous position
         // if the captured piece is a king, remove it
         // if this piece was just promoted, or if it is a king that just moved, register it at the target position
         let is_king = (self.kings & 1 << src) != 0;
+        let (crowned, jumps) = self.crowning(promoted, is_king);
 
         let kings = ((self.kings & !(1 << src)) & !(1 << captured))
-            | (u32::from(is_king || promoted) << tgt);
+            | (u32::from(is_king || crowned) << tgt);
 
-        if capture {
-            let result = BitBoard::new(current, others, team, kings).get(turn);
+        let board = BitBoard::new(current, others, team, kings, self.rules);
+        (board, crowned, capture && jumps)
+    }
 
-            result.into_iter().for_each(|mut actions| {
-                if let Some(act) = actions.peek(actions.len() - 1) {
-                    let is_cycle = act.tgt == parent.src;
-                    if act.capture && !is_cycle {
-                        actions.prepend(parent).unwrap();
-                        func(actions);
-                    }
-                }
+    /// Whether the jump `action` continues into (atleast) one longer jump sequence
+    fn continues(&self, action: Action, captured: u8, turn: Player) -> bool {
+        let (board, _, jumps) = self.after(action, captured);
+        jumps && board.can_jump(turn)
+    }
+
+    /// Visits every path starting with the move `action`, each of them following the jumps of `prefix`
+    fn next(
+        &self,
+        action: Action,
+        captured: u8,
+        turn: Player,
+        prefix: &ActionPath,
+        visit: &mut dyn FnMut(ActionPath),
+    ) {
+        let Action {
+            src, tgt, promoted, ..
+        } = action;
+        if tgt >= 32 {
+            return;
+        }
+
+        let capture = captured != 0;
+        let parent = Action::new(src, tgt, capture, promoted, U32);
+
+        // whether this jump continues into (atleast) one longer jump sequence
+        let mut continues = false;
+
+        // quiet moves never carry on, so the board after them is not needed
+        if capture && let (board, crowned, true) = self.after(action, captured) {
+            let mut path = *prefix;
+            path.append(Action {
+                promoted: crowned,
+                ..parent
+            })
+            .unwrap();
+
+            board.paths(turn, &path, true, &mut |path| {
+                continues = true;
+                visit(path);
             });
         }
 
-        func(parent.into());
+        self.finish(continues, prefix, parent, visit);
+    }
+
+    /// How a piece carries on after jumping (onto the opponent's base if `promoted`):
+    /// (whether it is crowned before its next jump, whether it can jump again at all).
+    /// Only a man reaching the base in the middle of a jump sequence depends on the promotion rule
+    fn crowning(&self, promoted: bool, king: bool) -> (bool, bool) {
+        match (promoted && !king, self.rules.promotion) {
+            (true, Promotion::Stop) => (true, false),
+            (true, Promotion::PassThrough) => (false, true),
+            _ => (promoted, true),
+        }
+    }
+
+    /// The occupied squares and the opponent's pieces left after jumping from `src` to `tgt` over `captured`.
+    /// The captured pieces stay on the board (as obstacles) until the jump sequence is completed,
+    /// so they can neither be jumped over again, nor landed on
+    fn jumped(occupied: u32, other: u32, src: u8, tgt: u8, captured: u8) -> (u32, u32) {
+        (
+            (occupied & !(1 << src)) | (1 << tgt),
+            other & !(1 << captured),
+        )
     }
 
+    /// Visits the path ending with the jump `action` after `prefix`, unless the jump `continues` while captures
+    /// are mandatory: a jump sequence must then be completed
+    fn finish<F: FnMut(ActionPath) + ?Sized>(
+        &self,
+        continues: bool,
+        prefix: &ActionPath,
+        action: Action,
+        visit: &mut F,
+    ) {
+        if !(continues && self.rules.capture == Capture::Mandatory) {
+            let mut path = *prefix;
+            path.append(action).unwrap();
+            visit(path);
+        }
+    }
+
+    /// Visits every path of the flying kings on `current` (only their jump sequences with `captures_only`),
+    /// each of them following the jumps of `prefix`
+    fn paths(
+        &self,
+        turn: Player,
+        prefix: &ActionPath,
+        captures_only: bool,
+        visit: &mut dyn FnMut(ActionPath),
+    ) {
+        let mandatory = self.rules.capture == Capture::Mandatory;
+        let flying = match self.rules.king_range {
+            KingRange::Flying => self.kings,
+            KingRange::Short => 0,
+        };
+
+        self.steps(captures_only, &mut |action, captured| {
+            // after capturing a piece, a flying king must land on a square from which it can continue capturing (if any)
+            if mandatory
+                && action.capture
+                && ((1 << action.src) & flying) != 0
+                && !self.continues(action, captured, turn)
+                && self.can_continue_after(action.src, turn, captured)
+            {
+                return;
+            }
+
+            self.next(action, captured, turn, prefix, visit);
+        });
+    }
+
+    /// Whether the flying king on `src` can continue capturing from any of the squares it can land on after capturing `captured`
+    fn can_continue_after(&self, src: u8, turn: Player, captured: u8) -> bool {
+        let mut continues = false;
+        self.fly(src, &mut |action, piece| {
+            continues |= piece == captured && action.capture && self.continues(action, piece, turn);
+        });
+        continues
+    }
+
+    /// The rank of a capture sequence for the majority capture rule, the higher the better:
+    /// (pieces captured, captured with a king, kings captured, how early the kings were captured)
+    fn rank(&self, path: &ActionPath) -> (usize, bool, u32, u32) {
+        let first = Action::from(path[0]);
+
+        match self.rules.majority {
+            Majority::Italian => {
+                let (kings, earliest) = path
+                    .iter()
+                    .map(|mv| Action::from(*mv))
+                    .map(|Action { src, tgt, .. }| self.captured(src, tgt))
+                    .enumerate()
+                    .filter(|(_, captured)| ((1 << captured) & self.kings) != 0)
+                    .fold((0, 0), |(kings, earliest), (i, _)| {
+                        (kings + 1, earliest | (1 << (31 - i)))
+                    });
+
+                let is_king = ((1 << first.src) & self.kings) != 0;
+                (path.len(), is_king, kings, earliest)
+            }
+            _ => (path.len(), false, 0, 0),
+        }
+    }
+
+    /// Returns all the moves the pieces on `current` can play, with the majority capture rule
+    /// (if any) applied to the capture sequences
     pub(crate) fn get(&self, turn: Player) -> Vec<ActionPath> {
         let mut mvs = vec![];
+        self.generate(turn, &mut |path| mvs.push(path));
+        mvs
+    }
 
-        // let empty = !(self.current | self.other | self.team);
-        let kings = self.current & self.kings;
+    /// Writes all the moves the pieces on `current` can play into the list (just like `get`), without allocating
+    pub(crate) fn fill(&self, turn: Player, list: &mut MoveList) {
+        list.clear();
+        self.generate(turn, &mut |path| list.push(path));
+    }
 
-        for src in Bits::from(self.current) {
-            let is_king = ((1 << src) & kings) != 0;
+    /// Visits all the moves the pieces on `current` can play, with the mandatory captures and the majority
+    /// capture rule (if any) applied
+    pub(crate) fn generate<F: FnMut(ActionPath)>(&self, turn: Player, visit: &mut F) {
+        // only the capturing moves are available to the player if there is atleast one of them
+        let captures_only = self.rules.capture == Capture::Mandatory && self.can_jump(turn);
 
-            let mut actions = self.shift(src, turn);
+        if self.rules.majority == Majority::Off {
+            return self.moves(turn, captures_only, visit);
+        }
 
-            if is_king {
-                actions.extend_from_slice(&self.shift(src, !turn));
+        // the best capture sequence has to be known before any of them is visited
+        let mut best = None;
+        self.moves(turn, captures_only, &mut |path| {
+            if path.is_capture() {
+                best = best.max(Some(self.rank(&path)));
             }
+        });
 
-            for (action, captured) in actions {
-                self.next(action, captured, turn, |path| {
-                    mvs.push(path);
-                });
+        self.moves(turn, captures_only, &mut |path| {
+            if !path.is_capture() || Some(self.rank(&path)) == best {
+                visit(path);
             }
+        });
+    }
+
+    /// Visits every path of the pieces on `current` (only their jump sequences with `captures_only`). The men and
+    /// the short range kings are moved all at once, direction by direction, and only the jumping ones carry on alone
+    fn moves<F: FnMut(ActionPath)>(&self, turn: Player, captures_only: bool, visit: &mut F) {
+        let (forward, backward) = Self::diagonals(turn);
+        let (men, short, flying) = self.pieces();
+
+        // the kings moving backwards are flagged as promoted when they land on their own base, while
+        // a man capturing backwards moves towards its own base, so it is never promoted by it
+        let (base, back_base) = (turn.opponent_base(), (!turn).opponent_base());
+        let back_men = men * u32::from(self.rules.men_capture_backwards);
+
+        for direction in forward {
+            self.jump(turn, men | short, direction, base, visit);
+        }
+        for direction in backward {
+            self.jump(turn, short, direction, back_base, visit);
+            self.jump(turn, back_men, direction, 0, visit);
         }
 
-        mvs
+        if !captures_only {
+            for direction in forward {
+                self.slide(men | short, direction, base, visit);
+            }
+            for direction in backward {
+                self.slide(short, direction, back_base, visit);
+            }
+        }
+
+        // the flying kings follow their paths on the board they see, where the other pieces of the team are obstacles
+        if flying != 0 {
+            let team = self.team | (self.current & !flying);
+            let board = BitBoard::new(flying, self.other, team, self.kings, self.rules);
+            board.paths(turn, &ActionPath::new(U32), captures_only, visit);
+        }
     }
 
-    pub(super) fn new(current: u32, other: u32, team: u32, kings: u32) -> Self {
+    pub(super) fn new(current: u32, other: u32, team: u32, kings: u32, rules: RuleSet) -> Self {
         Self {
             current,
             other,
             team,
             kings,
+            rules,
         }
     }
 }
@@ -185,6 +692,7 @@ impl From<(u32, u32, u32, u32)> for BitBoard {
             other: value.1,
             team: value.2,
             kings: value.3,
+            rules: RuleSet::default(),
         }
     }
 }
@@ -580,4 +1088,597 @@ mod tests {
 
         assert_eq!(new_board.kings, 0);
     }
+
+    #[test]
+    fn capturing_removes_the_captured_piece() {
+        let north = 1 << 22;
+        let south = 1 << 19;
+
+        let board = Board::with(
+            north,
+            south,
+            0,
+            Player::North,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        );
+
+        let action = get_path(vec![vec![(45, 31, true, false, U64)]])[0];
+        let new_board = board.play(action).unwrap();
+
+        assert_eq!(new_board.south, 0);
+        assert_eq!(new_board.north, 1 << 15);
+    }
+
+    #[test]
+    fn mandatory_captures_only_return_complete_jump_sequences() {
+        let south = 1 | 1 << 5 | 1 << 13 | 1u32 << 22;
+        let north = 1u32 << 27;
+
+        let board = Board::with(
+            north,
+            south,
+            0,
+            Player::North,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(RuleSet::english());
+        let received = board.options(Player::North);
+
+        let expected = get_path(vec![vec![
+            (54u8, 36u8, true, false, U64),
+            (36, 18, true, false, U64),
+            (18, 4, true, true, U64),
+        ]]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|path| assert!(received.contains(&path.transcode())));
+
+        let new_board = board.play(expected[0]).unwrap();
+        assert_eq!(new_board.south, 1);
+        assert_eq!(new_board.north, 1 << 2);
+        assert_eq!(new_board.kings, 1 << 2);
+    }
+
+    #[test]
+    fn mandatory_captures_exclude_quiet_moves_of_every_piece() {
+        let south = 1 << 12 | 1 << 13 | 1 << 20 | 1 << 10;
+        let north = 1 << 17 | 1 << 18 | 1 << 27 | 1 << 29;
+
+        let board = Board::with(
+            north,
+            south,
+            0,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(RuleSet::english());
+        let received = board.options(Player::South);
+
+        let expected = get_path(vec![
+            vec![(25u8, 43u8, true, false, U64)],
+            vec![
+                (27u8, 45u8, true, false, U64),
+                (45u8, 63u8, true, true, U64),
+            ],
+        ]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|x| assert!(received.contains(&(x.transcode()))));
+
+        let quiet = get_path(vec![vec![(20u8, 29u8, false, false, U64)]])[0];
+        let partial = get_path(vec![vec![(27u8, 45u8, true, false, U64)]])[0];
+
+        assert!(!board.is_valid(quiet, Player::South));
+        assert!(!board.is_valid(partial, Player::South));
+        assert!(board.play(quiet).is_err());
+        assert!(
+            board
+                .with_rules(RuleSet::casual())
+                .is_valid(quiet, Player::South)
+        );
+    }
+
+    #[test]
+    fn mandatory_captures_allow_quiet_moves_when_there_is_no_capture() {
+        let board = Board::new().with_rules(RuleSet::english());
+
+        assert_eq!(board.options(Player::South).len(), 7);
+    }
+
+    #[test]
+    fn flying_kings_slide_along_every_diagonal() {
+        let south = 1 << 13; // d4
+        let north = 1 << 28; // b8
+        let rules = RuleSet {
+            king_range: KingRange::Flying,
+            ..RuleSet::casual()
+        };
+
+        let board = Board::with(
+            north,
+            south,
+            south,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(rules);
+        let received = board.options(Player::South);
+
+        let expected = get_path(
+            [18, 9, 0, 36, 45, 54, 63, 20, 13, 6, 34, 41, 48]
+                .into_iter()
+                .map(|tgt| vec![(27u8, tgt, false, false, U64)])
+                .collect(),
+        );
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|x| assert!(received.contains(&x.transcode())));
+    }
+
+    #[test]
+    fn flying_kings_capture_from_a_distance() {
+        let south = 1; // a1
+        let north = 1 << 18; // e5
+        let rules = RuleSet {
+            king_range: KingRange::Flying,
+            ..RuleSet::casual()
+        };
+
+        let board = Board::with(
+            north,
+            south,
+            south,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(rules);
+        let received = board.options(Player::South);
+
+        let expected = get_path(vec![
+            vec![(0u8, 9u8, false, false, U64)],
+            vec![(0u8, 18u8, false, false, U64)],
+            vec![(0u8, 27u8, false, false, U64)],
+            vec![(0u8, 45u8, true, false, U64)],
+            vec![(0u8, 54u8, true, false, U64)],
+            vec![(0u8, 63u8, true, false, U64)],
+        ]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|x| assert!(received.contains(&x.transcode())));
+
+        let bitboard = BitBoard::new(south, north, 0, south, rules);
+        assert_eq!(bitboard.captured(0, 31), 18);
+        assert_eq!(bitboard.captured(0, 27), 18);
+
+        let new_board = board.play(expected[5].transcode()).unwrap();
+        assert_eq!(new_board.north, 0);
+        assert_eq!(new_board.south, 1 << 31);
+        assert_eq!(new_board.kings, 1 << 31);
+    }
+
+    #[test]
+    fn flying_kings_must_land_where_they_can_continue_capturing() {
+        let south = 1; // a1
+        let north = 1 << 18 | 1 << 26; // e5, e7
+        let rules = RuleSet {
+            king_range: KingRange::Flying,
+            ..RuleSet::english()
+        };
+
+        let board = Board::with(
+            north,
+            south,
+            south,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(rules);
+        let received = board.options(Player::South);
+
+        let expected = get_path(vec![vec![
+            (0u8, 45u8, true, false, U64),
+            (45u8, 59u8, true, false, U64),
+        ]]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|x| assert!(received.contains(&x.transcode())));
+
+        let new_board = board.play(expected[0]).unwrap();
+        assert_eq!(new_board.north, 0);
+        assert_eq!(new_board.south, 1 << 29);
+        assert_eq!(new_board.kings, 1 << 29);
+    }
+
+    #[test]
+    fn captured_pieces_block_a_flying_king_until_the_jump_sequence_ends() {
+        let south = 1; // a1
+        let north = 1 << 9 | 1 << 19 | 1 << 6 | 1 << 5; // c3, g5, f2, d2
+        let rules = RuleSet {
+            king_range: KingRange::Flying,
+            ..RuleSet::english()
+        };
+
+        let board = Board::with(
+            north,
+            south,
+            south,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(rules);
+        let received = board.options(Player::South);
+
+        // d2 can not be captured after f2, since the king would have to land on the (captured) piece on c3
+        let expected = get_path(vec![
+            vec![(0u8, 27u8, true, false, U64), (27u8, 6u8, true, false, U64)],
+            vec![
+                (0u8, 45u8, true, false, U64),
+                (45u8, 31u8, true, false, U64),
+                (31u8, 4u8, true, false, U64),
+            ],
+        ]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|x| assert!(received.contains(&x.transcode())));
+
+        let new_board = board.play(expected[1].transcode()).unwrap();
+        assert_eq!(new_board.north, 1 << 5);
+        assert_eq!(new_board.south, 1 << 2);
+    }
+
+    #[test]
+    fn majority_capture_only_returns_the_sequences_capturing_the_most_pieces() {
+        let south = 1 << 12 | 1 << 13 | 1 << 20 | 1 << 10;
+        let north = 1 << 17 | 1 << 18 | 1 << 27 | 1 << 29;
+        let rules = RuleSet {
+            majority: Majority::Quantity,
+            ..RuleSet::english()
+        };
+
+        let board = Board::with(
+            north,
+            south,
+            0,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(rules);
+        let received = board.options(Player::South);
+
+        let expected = get_path(vec![vec![
+            (27u8, 45u8, true, false, U64),
+            (45u8, 63u8, true, true, U64),
+        ]]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|x| assert!(received.contains(&(x.transcode()))));
+    }
+
+    #[test]
+    fn italian_majority_capture_prefers_capturing_with_a_king() {
+        let south = 1 << 9 | 1 << 11; // c3, g3
+        let north = 1 << 13 | 1 << 14; // d4, f4
+        let rules = RuleSet {
+            majority: Majority::Italian,
+            ..RuleSet::english()
+        };
+
+        let board = Board::with(
+            north,
+            south,
+            1 << 11,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        );
+
+        let board = board.with_rules(rules);
+        let received = board.options(Player::South);
+        let expected = get_path(vec![vec![(22u8, 36u8, true, false, U64)]]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|x| assert!(received.contains(&(x.transcode()))));
+
+        let quantity = board.with_rules(RuleSet {
+            majority: Majority::Quantity,
+            ..rules
+        });
+        assert_eq!(quantity.options(Player::South).len(), 2);
+    }
+
+    #[test]
+    fn italian_majority_capture_prefers_the_most_and_earliest_kings() {
+        let south = 1 | 1 << 2; // a1, e1
+        let north = 1 << 4 | 1 << 13 | 1 << 6 | 1 << 14; // b2, d4, f2, f4
+        let rules = RuleSet {
+            majority: Majority::Italian,
+            ..RuleSet::english()
+        };
+
+        let from_a1 = get_path(vec![vec![
+            (0u8, 18u8, true, false, U64),
+            (18u8, 36u8, true, false, U64),
+        ]])[0]
+            .transcode();
+        let from_e1 = get_path(vec![vec![
+            (4u8, 22u8, true, false, U64),
+            (22u8, 36u8, true, false, U64),
+        ]])[0]
+            .transcode();
+
+        let options = |kings: u32| {
+            Board::with(
+                north,
+                south,
+                kings,
+                Player::South,
+                Qmvs::default(),
+                #[cfg(feature = "history")]
+                Vec::with_capacity(0),
+            )
+            .with_rules(rules)
+            .options(Player::South)
+        };
+
+        // the same number of pieces (and kings) captured by both sequences
+        assert_eq!(options(0).len(), 2);
+        // most kings
+        assert_eq!(options(1 << 4 | 1 << 13), vec![from_a1]);
+        assert_eq!(options(1 << 6 | 1 << 14), vec![from_e1]);
+        // earliest king
+        assert_eq!(options(1 << 4 | 1 << 14), vec![from_a1]);
+        assert_eq!(options(1 << 13 | 1 << 6), vec![from_e1]);
+    }
+
+    #[test]
+    fn men_capture_backwards_but_only_move_forwards() {
+        let south = 1 << 13; // d4
+        let north = 1 << 9 | 1 << 28; // c3, b8
+        let rules = RuleSet {
+            men_capture_backwards: true,
+            ..RuleSet::casual()
+        };
+
+        let board = Board::with(
+            north,
+            south,
+            0,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        );
+        assert_eq!(board.options(Player::South).len(), 2);
+
+        let received = board.with_rules(rules).options(Player::South);
+        let expected = get_path(vec![
+            vec![(27u8, 34u8, false, false, U64)],
+            vec![(27u8, 36u8, false, false, U64)],
+            vec![(27u8, 9u8, true, false, U64)],
+        ]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|x| assert!(received.contains(&x.transcode())));
+
+        let backwards = get_path(vec![vec![(27u8, 20u8, false, false, U64)]])[0];
+        assert!(!received.contains(&backwards.transcode()));
+    }
+
+    #[test]
+    fn men_capture_backwards_in_the_middle_of_a_jump_sequence() {
+        let south = 1 << 9; // c3
+        let north = 1 << 13 | 1 << 14; // d4, f4
+        let rules = RuleSet {
+            men_capture_backwards: true,
+            ..RuleSet::english()
+        };
+
+        let board = Board::with(
+            north,
+            south,
+            0,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(rules);
+        let received = board.options(Player::South);
+
+        let expected = get_path(vec![vec![
+            (18u8, 36u8, true, false, U64),
+            (36u8, 22u8, true, false, U64),
+        ]]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|x| assert!(received.contains(&x.transcode())));
+
+        let new_board = board.play(expected[0]).unwrap();
+        assert_eq!(new_board.north, 0);
+        assert_eq!(new_board.south, 1 << 11);
+        assert_eq!(new_board.kings, 0);
+    }
+
+    #[test]
+    fn northern_men_capture_backwards_towards_the_north() {
+        let north = 1 << 12;
+        let south = 1 << 9 | 1 << 10 | 1 << 11 | 1 << 16;
+        let rules = RuleSet {
+            men_capture_backwards: true,
+            ..RuleSet::casual()
+        };
+
+        let board = Board::with(
+            north,
+            south,
+            0,
+            Player::North,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(rules);
+        let received = board.options(Player::North);
+
+        let expected = get_path(vec![
+            vec![(25u8, 16u8, false, false, U64)],
+            vec![(25u8, 11u8, true, false, U64)],
+            vec![(25u8, 11u8, true, false, U64), (11, 29, true, false, U64)],
+            vec![
+                (25u8, 11u8, true, false, U64),
+                (11, 29, true, false, U64),
+                (29, 15, true, false, U64),
+            ],
+        ]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|x| assert!(received.contains(&x.transcode())));
+    }
+
+    #[test]
+    fn promotion_ends_the_move_under_english_rules() {
+        let south = 1 << 20;
+        let north = 1 << 25 | 1 << 26 | 1 << 13;
+
+        let board = Board::with(
+            north,
+            south,
+            0,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(RuleSet::english());
+        let received = board.options(Player::South);
+
+        let expected = get_path(vec![vec![(41, 59, true, true, U64)]]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|mv| assert!(received.contains(&(mv.transcode()))));
+    }
+
+    #[test]
+    fn men_crowned_mid_capture_keep_capturing_as_flying_kings_under_russian_rules() {
+        let south = 1 << 20;
+        let north = 1 << 25 | 1 << 26 | 1 << 13;
+
+        let board = Board::with(
+            north,
+            south,
+            0,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(RuleSet::russian());
+        let received = board.options(Player::South);
+
+        let expected = get_path(
+            [18, 9, 0]
+                .into_iter()
+                .map(|tgt| {
+                    vec![
+                        (41, 59, true, true, U64),
+                        (59, 45, true, false, U64),
+                        (45, tgt, true, false, U64),
+                    ]
+                })
+                .collect(),
+        );
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|mv| assert!(received.contains(&(mv.transcode()))));
+
+        let new_board = board.play(expected[2]).unwrap();
+        assert_eq!(new_board.north, 0);
+        assert_eq!(new_board.kings, 1);
+    }
+
+    #[test]
+    fn men_passing_through_the_opponents_base_are_not_crowned_under_brazilian_rules() {
+        let south = 1 << 20;
+        let north = 1 << 25 | 1 << 26 | 1 << 13;
+
+        let board = Board::with(
+            north,
+            south,
+            0,
+            Player::South,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::with_capacity(0),
+        )
+        .with_rules(RuleSet::brazilian());
+        let received = board.options(Player::South);
+
+        let expected = get_path(vec![vec![
+            (41, 59, true, false, U64),
+            (59, 45, true, false, U64),
+        ]]);
+
+        assert_eq!(received.len(), expected.len());
+        expected
+            .iter()
+            .for_each(|mv| assert!(received.contains(&(mv.transcode()))));
+
+        let new_board = board.play(expected[0]).unwrap();
+        assert_eq!(new_board.north, 1 << 13);
+        assert_eq!(new_board.south, 1 << 22);
+        assert_eq!(new_board.kings, 0);
+    }
+
+    #[test]
+    fn neighbours_follow_the_diagonals() {
+        for (direction, neighbours) in BitBoard::DIRECTIONS.into_iter().zip(BitBoard::NEIGHBOURS) {
+            for sq in 0..32 {
+                let next = BitBoard::step(sq, direction).unwrap_or(32);
+                assert_eq!(neighbours[sq as usize], next, "square {sq}");
+            }
+        }
+    }
 }
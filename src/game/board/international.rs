@@ -0,0 +1,570 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Index;
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    game::{
+        model::{action::Action, path::ActionPath, player::Player},
+        utils::{ApiError, Qmvs},
+    },
+    mcts::{
+        algo::{state::State, tree_search::MCTS},
+        utils::{
+            limit::Limit,
+            reward::{DrawRule, Reward},
+            skill_level::SkillLevel,
+            strength::Strength,
+        },
+    },
+};
+
+use super::scale::Scale;
+
+/// (row, col) steps of the four diagonals
+type Direction = (i8, i8);
+
+const NORTH_EAST: Direction = (1, 1);
+const NORTH_WEST: Direction = (1, -1);
+const SOUTH_EAST: Direction = (-1, 1);
+const SOUTH_WEST: Direction = (-1, -1);
+
+const DIRECTIONS: [Direction; 4] = [NORTH_EAST, NORTH_WEST, SOUTH_EAST, SOUTH_WEST];
+
+/// Number of rows (and columns) on the board
+const SIZE: i8 = 10;
+/// Every one of the 50 playable (dark) squares
+const SQUARES: u64 = (1 << 50) - 1;
+const ROW_1_MASK: u64 = 0x1F;
+const ROW_10_MASK: u64 = 0x1F << 45;
+/// The number of king moves (per player) without any capture, or man moving, after which the game is a draw
+const QUIET_MVS: u8 = 25;
+/// The number of moves a lone king can hold out against (at most) three kings, before the game is a draw
+const ENDGAME_MVS: u8 = 16;
+
+/// International draughts, played on a 10x10 board with 20 pieces per player.
+/// Only the 50 dark squares are playable, they are numbered from 0 (A1) to 49 (J10), 5 squares per row.
+/// The rules of the variant are always applied on this board: men move forward but capture in every direction,
+/// kings fly, captures are mandatory and the sequence capturing the most pieces must be played (majority capture),
+/// a man is only promoted if it ends its move on the opponent's base and the game is a draw after 25 moves
+/// (per player) with only kings, and without any capture
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct InternationalBoard {
+    /// north pieces and north kings
+    pub north: u64,
+    /// south pieces and south kings
+    pub south: u64,
+    /// north and south kings
+    pub kings: u64,
+    /// South always plays the first move
+    pub turn: Player,
+    /// The number of king moves played without any capture (or man moving) so far
+    pub qmvs: Qmvs,
+}
+
+#[cfg_attr(feature = "web", wasm_bindgen)]
+impl InternationalBoard {
+    /// Creates a brand new International draughts board with 20 pieces per player
+    #[cfg_attr(feature = "web", wasm_bindgen(constructor))]
+    pub fn new() -> Self {
+        Self {
+            north: SQUARES & !((1 << 30) - 1),
+            south: (1 << 20) - 1,
+            kings: 0,
+            turn: Player::South,
+            qmvs: Qmvs::default(),
+        }
+    }
+
+    /// Creates an International draughts board using the provided information (args), each bit of
+    /// **north**, **south** and **kings** represents one of the 50 playable squares
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn with(north: u64, south: u64, kings: u64, turn: Player, qmvs: Qmvs) -> Self {
+        Self {
+            north: north & SQUARES,
+            south: south & SQUARES,
+            kings: kings & SQUARES,
+            turn,
+            qmvs,
+        }
+    }
+
+    /// The squares (indexes) of the set bits
+    fn squares(mut bits: u64) -> impl Iterator<Item = u8> {
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+
+            let sq = bits.trailing_zeros() as u8;
+            bits &= bits - 1;
+            Some(sq)
+        })
+    }
+
+    /// (row, col) of the square
+    fn coords(sq: u8) -> (i8, i8) {
+        let row = (sq / 5) as i8;
+        (row, (2 * (sq % 5)) as i8 + (row % 2))
+    }
+
+    /// Returns the next square after `sq` along the diagonal (direction),
+    /// or None if `sq` is already on the edge of the board in that direction
+    fn step(sq: u8, (row_step, col_step): Direction) -> Option<u8> {
+        let (row, col) = Self::coords(sq);
+        let (row, col) = (row + row_step, col + col_step);
+
+        if !(0..SIZE).contains(&row) || !(0..SIZE).contains(&col) {
+            return None;
+        }
+
+        Some((row * 5 + col / 2) as u8)
+    }
+
+    fn promotion(turn: Player) -> u64 {
+        match turn {
+            Player::North => ROW_1_MASK,
+            Player::South => ROW_10_MASK,
+        }
+    }
+
+    /// The directions a man of the player moves in (men always capture in every direction)
+    fn forward(turn: Player) -> [Direction; 2] {
+        match turn {
+            Player::North => [SOUTH_EAST, SOUTH_WEST],
+            Player::South => [NORTH_EAST, NORTH_WEST],
+        }
+    }
+
+    /// Returns the opponent's piece captured by jumping from `src` to `tgt`, i.e. the only opponent's
+    /// piece on the diagonal between them
+    fn captured(&self, src: u8, tgt: u8, turn: Player) -> Option<u8> {
+        DIRECTIONS.into_iter().find_map(|direction| {
+            let mut sq = src;
+            let mut captured = None;
+
+            while let Some(next) = Self::step(sq, direction) {
+                if next == tgt {
+                    return captured;
+                }
+
+                if ((1 << next) & self[!turn]) != 0 {
+                    captured = Some(next);
+                }
+
+                sq = next;
+            }
+
+            None
+        })
+    }
+
+    /// Collects every (complete) jump sequence the piece standing on `sq` can extend `path` with.
+    /// The captured (dead) pieces stay on the board until the sequence is completed, so they can neither be
+    /// captured again nor jumped over
+    fn jumps(
+        &self,
+        sq: u8,
+        king: bool,
+        empty: u64,
+        dead: u64,
+        path: ActionPath,
+    ) -> Vec<ActionPath> {
+        let opponent = self[!self.turn] & !dead;
+        let mut paths = vec![];
+
+        for direction in DIRECTIONS {
+            let mut next = Self::step(sq, direction);
+
+            while king && next.is_some_and(|n| ((1 << n) & empty) != 0) {
+                next = next.and_then(|n| Self::step(n, direction));
+            }
+
+            let Some(victim) = next.filter(|n| ((1 << n) & opponent) != 0) else {
+                continue;
+            };
+
+            let mut land = Self::step(victim, direction);
+
+            while let Some(tgt) = land.filter(|t| ((1 << t) & empty) != 0) {
+                let mut path = path;
+                path.append(Action::new(sq, tgt, true, false, Scale::U50))
+                    .unwrap();
+
+                paths.extend(self.jumps(tgt, king, empty, dead | (1 << victim), path));
+
+                if !king {
+                    break;
+                }
+
+                land = Self::step(tgt, direction);
+            }
+        }
+
+        if paths.is_empty() && !path.is_empty() {
+            paths.push(path);
+        }
+
+        paths
+    }
+
+    /// Marks the last move of a man's path as a promotion, if it ends the move on the opponent's base
+    fn promote(path: &mut ActionPath, turn: Player) {
+        let last = path.len - 1;
+        let mut action = Action::from(path.mvs[last]);
+        action.promoted = ((1 << action.tgt) & Self::promotion(turn)) != 0;
+        path.mvs[last] = action.into();
+    }
+
+    /// Returns all the possible options(moves) that the selected user can play
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn options(&self, turn: Player) -> Vec<ActionPath> {
+        let board = Self { turn, ..*self };
+        let occupied = self.north | self.south;
+        let mut captures = vec![];
+
+        for src in Self::squares(board[turn]) {
+            let king = ((1 << src) & self.kings) != 0;
+            // the moving piece leaves its square, which it is then allowed to pass through (or land on)
+            let empty = SQUARES & !(occupied & !(1 << src));
+
+            let mut paths = board.jumps(src, king, empty, 0, ActionPath::new(Scale::U50));
+
+            if !king {
+                paths.iter_mut().for_each(|path| Self::promote(path, turn));
+            }
+
+            captures.extend(paths);
+        }
+
+        // majority capture: only the sequences capturing the most pieces can be played
+        if let Some(most) = captures.iter().map(|path| path.len()).max() {
+            captures.retain(|path| path.len() == most);
+            return captures;
+        }
+
+        let mut mvs = vec![];
+        let empty = SQUARES & !occupied;
+
+        for src in Self::squares(board[turn]) {
+            let king = ((1 << src) & self.kings) != 0;
+            let directions = match king {
+                true => DIRECTIONS.to_vec(),
+                false => Self::forward(turn).to_vec(),
+            };
+
+            for direction in directions {
+                let mut next = Self::step(src, direction);
+
+                while let Some(tgt) = next.filter(|t| ((1 << t) & empty) != 0) {
+                    let promoted = !king && ((1 << tgt) & Self::promotion(turn)) != 0;
+                    mvs.push(Action::new(src, tgt, false, promoted, Scale::U50).into());
+
+                    if !king {
+                        break;
+                    }
+
+                    next = Self::step(tgt, direction);
+                }
+            }
+        }
+
+        mvs
+    }
+
+    /// Checks whether the move (ActionPath) about to be played is valid based on the board's current state
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn is_valid(&self, path: ActionPath, turn: Player) -> bool {
+        path.len > 0 && path.scale == Scale::U50 && self.options(turn).contains(&path)
+    }
+
+    /// This returns a new Board state (the new board state) after the move (ActionPath) is applied to the board
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn play(&self, path: ActionPath) -> Option<Self> {
+        if !self.is_valid(path, self.turn) {
+            return None;
+        }
+
+        let turn = self.turn;
+        let first = Action::from(path[0]);
+        let last = Action::from(path[path.len - 1]);
+        let (src_mask, tgt_mask) = (1u64 << first.src, 1u64 << last.tgt);
+
+        let captured = path.iter().fold(0u64, |captured, mv| {
+            let Action { src, tgt, .. } = Action::from(*mv);
+            captured | self.captured(src, tgt, turn).map_or(0, |sq| 1 << sq)
+        });
+
+        let is_king = (self.kings & src_mask) != 0;
+        let us = (self[turn] & !src_mask) | tgt_mask;
+        let them = self[!turn] & !captured;
+        let kings =
+            (self.kings & !src_mask & !captured) | (u64::from(is_king || last.promoted) * tgt_mask);
+
+        let (north, south) = match turn {
+            Player::North => (us, them),
+            Player::South => (them, us),
+        };
+
+        // only king moves without any capture count towards a draw
+        let cp = u8::from(is_king && captured == 0);
+        let mut qmvs = self.qmvs;
+        qmvs[turn] = (qmvs[turn] + 1) * cp;
+        qmvs[!turn] *= cp;
+
+        Some(Self::with(north, south, kings, !turn, qmvs))
+    }
+
+    /// Generates the next best move based on the provided MCTS configuration
+    /// NB: Only use this method when you're trying to get a bot's next move
+    /// exp: exploration constant for MCTS
+    /// col: cost of losing (recommended ==> -1.25)
+    /// limit: How long should MCTS think (in ms)? (recommended 100)
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn best_mv(&self, exp: f64, col: f64, limit: u128) -> ActionPath {
+        let skills = SkillLevel::One(Strength::new(exp, col, Limit::Time(limit)));
+        let mut mcts = MCTS::new(*self, self.turn, vec![Player::North, Player::South], skills);
+        mcts.run()
+    }
+}
+
+impl Default for InternationalBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State<ActionPath, Player, ApiError> for InternationalBoard {
+    fn is_terminal(&self) -> bool {
+        self.get_reward() != Reward::Continue
+    }
+
+    fn get_reward(&self) -> Reward<Player> {
+        if self.north == 0 {
+            return Reward::WonBy(Player::South);
+        }
+
+        if self.south == 0 {
+            return Reward::WonBy(Player::North);
+        }
+
+        let Qmvs { north: n, south: s } = self.qmvs;
+        if n >= QUIET_MVS || s >= QUIET_MVS {
+            return Reward::Draw(DrawRule::QuietMoves);
+        }
+
+        let (lone, other) = match self.north.count_ones() < self.south.count_ones() {
+            true => (self.north, self.south),
+            false => (self.south, self.north),
+        };
+        let is_endgame =
+            (lone | other) == self.kings && lone.count_ones() == 1 && other.count_ones() <= 3;
+        if is_endgame && (n >= ENDGAME_MVS || s >= ENDGAME_MVS) {
+            return Reward::Draw(DrawRule::Endgame);
+        }
+
+        if self.get_actions().is_empty() {
+            return Reward::WonBy(!self.turn);
+        }
+
+        Reward::Continue
+    }
+
+    fn apply_action(&self, action: &ActionPath) -> Result<(Self, Player), ApiError> {
+        self.play(*action)
+            .map(|state| (state, state.turn))
+            .ok_or(ApiError::IllegalMove)
+    }
+
+    fn get_current_player(&self) -> &Player {
+        &self.turn
+    }
+
+    fn view(&self) -> String {
+        self.to_string()
+    }
+
+    fn get_actions(&self) -> Vec<ActionPath> {
+        self.options(self.turn)
+    }
+}
+
+impl Index<Player> for InternationalBoard {
+    type Output = u64;
+
+    fn index(&self, index: Player) -> &Self::Output {
+        match index {
+            Player::North => &self.north,
+            Player::South => &self.south,
+        }
+    }
+}
+
+impl std::fmt::Display for InternationalBoard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line = "-".repeat(63);
+        writeln!(f, "{line}")?;
+
+        for row in (0..SIZE).rev() {
+            write!(f, "{:>2} |", row + 1)?;
+
+            for col in 0..SIZE {
+                let is_dark = (row + col) % 2 == 0;
+                let piece = match is_dark {
+                    true => {
+                        let cell = 1u64 << (row * 5 + col / 2);
+
+                        let is_king = (self.kings & cell) != 0;
+                        let is_south = (self.south & cell) != 0;
+                        let is_north = (self.north & cell) != 0;
+
+                        // White (south) always starts in International draughts
+                        match (is_south, is_north, is_king) {
+                            (true, false, false) => "W",
+                            (true, false, true) => "WK",
+                            (false, true, false) => "B",
+                            (false, true, true) => "BK",
+                            _ => "",
+                        }
+                    }
+                    false => "",
+                };
+
+                write!(f, " {:^3} |", piece)?;
+            }
+
+            writeln!(f)?;
+            writeln!(f, "{line}")?;
+        }
+
+        writeln!(
+            f,
+            "   |  A  |  B  |  C  |  D  |  E  |  F  |  G  |  H  |  I  |  J  |"
+        )?;
+        writeln!(f, "{line}")?;
+
+        writeln!(f, "Turn: {:?}", self.turn)?;
+        writeln!(f, "Quiet moves: {:?}", self.qmvs)?;
+        writeln!(f, "South: {:013x}", self.south)?;
+        writeln!(f, "North: {:013x}", self.north)?;
+        writeln!(f, "Kings: {:013x}", self.kings)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(input: Vec<(u8, u8, bool, bool)>) -> ActionPath {
+        let mut path = ActionPath::new(Scale::U50);
+        input.into_iter().for_each(|(src, tgt, capture, promoted)| {
+            path.append(Action::new(src, tgt, capture, promoted, Scale::U50))
+                .unwrap()
+        });
+        path
+    }
+
+    #[test]
+    fn should_return_all_possible_moves_in_the_base_position() {
+        let board = InternationalBoard::new();
+
+        assert_eq!(board.north.count_ones(), 20);
+        assert_eq!(board.south.count_ones(), 20);
+        assert_eq!(board.options(Player::South).len(), 9);
+        assert_eq!(board.options(Player::North).len(), 9);
+    }
+
+    #[test]
+    fn men_capture_backwards() {
+        let south = 1 << 22; // e5
+        let north = 1 << 16 | 1 << 49; // d4, j10
+
+        let board = InternationalBoard::with(north, south, 0, Player::South, Qmvs::default());
+        let received = board.options(Player::South);
+
+        assert_eq!(received, vec![path(vec![(22, 11, true, false)])]);
+    }
+
+    #[test]
+    fn only_the_sequence_capturing_the_most_pieces_can_be_played() {
+        let south = 1 | 1 << 4; // a1, i1
+        let north = 1 << 5 | 1 << 16 | 1 << 8; // b2, d4, h2
+
+        let board = InternationalBoard::with(north, south, 0, Player::South, Qmvs::default());
+        let received = board.options(Player::South);
+
+        let expected = path(vec![(0, 11, true, false), (11, 22, true, false)]);
+        assert_eq!(received, vec![expected]);
+
+        let new_board = board.play(expected).unwrap();
+        assert_eq!(new_board.north, 1 << 8);
+        assert_eq!(new_board.south, 1 << 4 | 1 << 22);
+        assert_eq!(new_board.turn, Player::North);
+    }
+
+    #[test]
+    fn men_are_only_promoted_when_they_end_their_move_on_the_opponents_base() {
+        let south = 1 << 36; // d8
+        let north = 1 << 42 | 1 << 43; // e9, g9
+
+        let board = InternationalBoard::with(north, south, 0, Player::South, Qmvs::default());
+        let expected = path(vec![(36, 47, true, false), (47, 38, true, false)]);
+        assert_eq!(board.options(Player::South), vec![expected]);
+
+        let new_board = board.play(expected).unwrap();
+        assert_eq!(new_board.kings, 0);
+        assert_eq!(new_board.get_reward(), Reward::WonBy(Player::South));
+
+        let board = InternationalBoard::with(1 << 42, south, 0, Player::South, Qmvs::default());
+        let expected = path(vec![(36, 47, true, true)]);
+        assert_eq!(board.options(Player::South), vec![expected]);
+
+        let new_board = board.play(expected).unwrap();
+        assert_eq!(new_board.kings, 1 << 47);
+    }
+
+    #[test]
+    fn flying_kings_capture_from_a_distance() {
+        let south = 1; // a1
+        let north = 1 << 16; // d4
+
+        let board = InternationalBoard::with(north, south, south, Player::South, Qmvs::default());
+        let received = board.options(Player::South);
+
+        let expected = [22, 27, 33, 38, 44, 49]
+            .into_iter()
+            .map(|tgt| path(vec![(0, tgt, true, false)]))
+            .collect::<Vec<_>>();
+
+        assert_eq!(received.len(), expected.len());
+        expected.iter().for_each(|x| assert!(received.contains(x)));
+
+        let new_board = board.play(expected[5]).unwrap();
+        assert_eq!(new_board.north, 0);
+        assert_eq!(new_board.kings, 1 << 49);
+    }
+
+    #[test]
+    fn only_king_moves_without_captures_count_towards_a_draw() {
+        let south = 1 | 1 << 10;
+        let north = 1 << 49;
+        let qmvs = Qmvs {
+            north: 24,
+            south: 24,
+        };
+
+        let board = InternationalBoard::with(north, south, south | north, Player::South, qmvs);
+        let new_board = board.play(path(vec![(0, 5, false, false)])).unwrap();
+        assert_eq!(new_board.qmvs.south, 25);
+        assert_eq!(new_board.get_reward(), Reward::Draw(DrawRule::QuietMoves));
+
+        let board = InternationalBoard::with(north, south, north, Player::South, qmvs);
+        let new_board = board.play(path(vec![(0, 5, false, false)])).unwrap();
+        assert_eq!(new_board.qmvs, Qmvs::default());
+        assert_eq!(new_board.get_reward(), Reward::Continue);
+    }
+}
@@ -0,0 +1,361 @@
+use crate::game::{
+    board::state::Board,
+    model::{movelist::MoveList, path::ActionPath},
+};
+
+/// The number of positions (leaf nodes) reached after playing every sequence of `depth` moves from this board.
+/// Comparing these counts with published ones is the usual way of checking a move generator, since a single
+/// missing or extra move changes the totals.
+/// Positions where the game has ended (no moves left) are not counted, while draws are ignored
+/// ```rust
+/// use tiqtak::{Board, RuleSet, perft};
+///
+/// let board = Board::new().with_rules(RuleSet::english());
+/// assert_eq!(perft(&board, 3), 302);
+/// ```
+pub fn perft(board: &Board, depth: u8) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    // the moves are made and unmade on a single copy of the board, and every ply fills the same list of moves
+    // again at each of its nodes, instead of creating (and clearing) one for every node
+    #[cfg(feature = "history")]
+    let mut board = board.clone();
+    #[cfg(not(feature = "history"))]
+    let mut board = *board;
+    let mut lists: Vec<MoveList> = (0..depth).map(|_| MoveList::new()).collect();
+
+    count(&mut board, &mut lists)
+}
+
+fn count(board: &mut Board, lists: &mut [MoveList]) -> u64 {
+    let Some((options, lists)) = lists.split_first_mut() else {
+        return 1;
+    };
+    board.generate(board.turn, options);
+
+    // the moves of the last ply only need to be counted, not played
+    if lists.is_empty() {
+        return options.len() as u64;
+    }
+
+    options
+        .iter()
+        .map(|option| {
+            let undo = board.make(option);
+            let nodes = count(board, lists);
+            board.unmake(undo);
+            nodes
+        })
+        .sum()
+}
+
+/// The perft count (see `perft`) below each of the moves available on this board, in the order the board
+/// generates them. When a count differs from a reference one, the move whose subtree differs can be played
+/// and divided again, until the missing or extra move is found
+/// ```rust
+/// use tiqtak::{Board, RuleSet, divide};
+///
+/// let board = Board::new().with_rules(RuleSet::english());
+/// for (mv, count) in divide(&board, 3) {
+///     println!("{mv}: {count}");
+/// }
+/// ```
+pub fn divide(board: &Board, depth: u8) -> Vec<(ActionPath, u64)> {
+    if depth == 0 {
+        return Vec::new();
+    }
+
+    board
+        .options(board.turn)
+        .into_iter()
+        .map(|option| (option, perft(&board.apply(option), depth - 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod perft {
+    use crate::{
+        Board,
+        game::board::{
+            perft::{divide, perft},
+            rules::{Capture, RuleSet},
+        },
+    };
+
+    /// Published perft counts of English draughts (mandatory captures) from the initial position
+    const ENGLISH: [u64; 9] = [1, 7, 49, 302, 1469, 7361, 36768, 179740, 845931];
+
+    #[test]
+    fn english_draughts_counts_from_the_initial_position() {
+        let board = Board::new().with_rules(RuleSet::english());
+
+        for (depth, count) in ENGLISH.iter().enumerate() {
+            assert_eq!(perft(&board, depth as u8), *count, "depth {depth}");
+        }
+    }
+
+    /// Published perft counts of Russian draughts (flying kings, men capturing backwards) from the initial position
+    const RUSSIAN: [u64; 7] = [1, 7, 49, 302, 1469, 7482, 37986];
+
+    #[test]
+    fn russian_draughts_counts_from_the_initial_position() {
+        let board = Board::new().with_rules(RuleSet::russian());
+
+        for (depth, count) in RUSSIAN.iter().enumerate() {
+            assert_eq!(perft(&board, depth as u8), *count, "depth {depth}");
+        }
+    }
+
+    /// A deliberately naive mover for English draughts (men moving and capturing forwards, short range kings,
+    /// promotion ending the move), written square by square on an 8x8 grid and sharing no code with the bitboard
+    /// generator. No published perft counts could be found for 8x8 positions other than the initial one, so the
+    /// counts of the other positions are checked against this mover instead of numbers taken from the generator
+    mod reference {
+        /// The pieces on the squares 1 to 32: 1 for a southern (black) man, 2 for a king, negative for the north
+        type Squares = [i8; 32];
+
+        fn coordinates(square: usize) -> (i32, i32) {
+            let row = (square / 4) as i32;
+            (row, 2 * (square % 4) as i32 + (row + 1) % 2)
+        }
+
+        fn square(row: i32, col: i32) -> Option<usize> {
+            let inside = (0..8).contains(&row) && (0..8).contains(&col) && (row + col) % 2 == 1;
+            inside.then(|| (row * 4 + col / 2) as usize)
+        }
+
+        /// The squares and the side to move (1 for the south, -1 for the north) of a FEN such as `B:W18,K19:B14`
+        pub(super) fn from_fen(fen: &str) -> (Squares, i8) {
+            let mut squares = [0; 32];
+            let mut fields = fen.split(':');
+            let side = if fields.next() == Some("B") { 1 } else { -1 };
+
+            for field in fields {
+                let sign = if field.starts_with('B') { 1 } else { -1 };
+                for piece in field[1..].split(',') {
+                    let (value, number) = match piece.strip_prefix('K') {
+                        Some(number) => (2 * sign, number),
+                        None => (sign, piece),
+                    };
+                    let (first, last) = number.split_once('-').unwrap_or((number, number));
+                    for number in first.parse::<usize>().unwrap()..=last.parse().unwrap() {
+                        squares[number - 1] = value;
+                    }
+                }
+            }
+
+            (squares, side)
+        }
+
+        /// The diagonals a piece moves along: forwards only for a man, every one of them for a king
+        fn directions(piece: i8) -> Vec<(i32, i32)> {
+            let forward = piece.signum() as i32;
+            match piece.abs() {
+                1 => vec![(forward, -1), (forward, 1)],
+                _ => vec![(1, -1), (1, 1), (-1, -1), (-1, 1)],
+            }
+        }
+
+        fn promotes(piece: i8, square: usize) -> bool {
+            let row = coordinates(square).0;
+            piece.abs() == 1 && row == if piece > 0 { 7 } else { 0 }
+        }
+
+        /// The board after the piece on `from` moved to the last square of `path`, taking the `captured` pieces
+        fn finish(squares: &Squares, from: usize, path: &[usize], captured: &[usize]) -> Squares {
+            let mut next = *squares;
+            let (piece, to) = (squares[from], *path.last().unwrap());
+
+            next[from] = 0;
+            captured.iter().for_each(|square| next[*square] = 0);
+            next[to] = if promotes(piece, to) {
+                2 * piece
+            } else {
+                piece
+            };
+            next
+        }
+
+        /// Every jump sequence of the piece on `from` carrying on from `at`. With mandatory captures only the
+        /// complete sequences are moves, otherwise every stop of a sequence is a move of its own
+        fn jumps(
+            squares: &Squares,
+            from: usize,
+            (path, captured): (&mut Vec<usize>, &mut Vec<usize>),
+            mandatory: bool,
+            moves: &mut Vec<Squares>,
+        ) {
+            let piece = squares[from];
+            let at = *path.last().unwrap();
+            let (row, col) = coordinates(at);
+            let mut extended = false;
+
+            // a man reaching the last row is crowned, which ends its move
+            let crowned = path.len() > 1 && promotes(piece, at);
+            for (dr, dc) in directions(piece).into_iter().filter(|_| !crowned) {
+                let (Some(over), Some(land)) = (
+                    square(row + dr, col + dc),
+                    square(row + 2 * dr, col + 2 * dc),
+                ) else {
+                    continue;
+                };
+
+                let opponent = squares[over].signum() == -piece.signum();
+                let empty = squares[land] == 0 || land == from;
+                if opponent && !captured.contains(&over) && empty {
+                    extended = true;
+                    path.push(land);
+                    captured.push(over);
+                    jumps(squares, from, (path, captured), mandatory, moves);
+                    path.pop();
+                    captured.pop();
+                }
+            }
+
+            if path.len() > 1 && !(extended && mandatory) {
+                moves.push(finish(squares, from, path, captured));
+            }
+        }
+
+        /// The boards after every move of the side to move, one per move (two moves can reach the same board)
+        fn moves(squares: &Squares, side: i8, mandatory: bool) -> Vec<Squares> {
+            let mut captures = vec![];
+            let mut quiet = vec![];
+
+            for from in (0..32).filter(|square| squares[*square].signum() == side) {
+                jumps(
+                    squares,
+                    from,
+                    (&mut vec![from], &mut vec![]),
+                    mandatory,
+                    &mut captures,
+                );
+
+                let (row, col) = coordinates(from);
+                for (dr, dc) in directions(squares[from]) {
+                    if let Some(to) = square(row + dr, col + dc).filter(|to| squares[*to] == 0) {
+                        quiet.push(finish(squares, from, &[to], &[]));
+                    }
+                }
+            }
+
+            if !mandatory || captures.is_empty() {
+                captures.extend(quiet);
+            }
+            captures
+        }
+
+        pub(super) fn perft(squares: &Squares, side: i8, depth: u8, mandatory: bool) -> u64 {
+            let moves = moves(squares, side, mandatory);
+            match depth {
+                0 => 1,
+                1 => moves.len() as u64,
+                _ => moves
+                    .iter()
+                    .map(|next| perft(next, -side, depth - 1, mandatory))
+                    .sum(),
+            }
+        }
+    }
+
+    /// Checks the perft counts of the position against the reference mover, with mandatory and with optional captures
+    fn agrees_with_the_reference(fen: &str, depth: u8) {
+        let (squares, side) = reference::from_fen(fen);
+        let english = Board::from_fen(fen).unwrap().with_rules(RuleSet::english());
+        let optional = Board::from_fen(fen).unwrap().with_rules(RuleSet {
+            capture: Capture::Optional,
+            ..RuleSet::english()
+        });
+
+        for depth in 1..=depth {
+            let expected = reference::perft(&squares, side, depth, true);
+            assert_eq!(perft(&english, depth), expected, "{fen} depth {depth}");
+
+            let expected = reference::perft(&squares, side, depth, false);
+            assert_eq!(
+                perft(&optional, depth),
+                expected,
+                "{fen} optional, depth {depth}"
+            );
+        }
+    }
+
+    #[test]
+    fn the_reference_mover_agrees_with_the_published_counts() {
+        let (squares, side) = reference::from_fen("B:W21-32:B1-12");
+
+        for (depth, count) in ENGLISH.iter().enumerate().take(8) {
+            assert_eq!(reference::perft(&squares, side, depth as u8, true), *count);
+        }
+    }
+
+    #[test]
+    fn optional_captures_count_every_stop_of_a_jump_sequence() {
+        let casual = Board::new();
+        assert_eq!(casual.rules().capture, Capture::Optional);
+
+        agrees_with_the_reference("B:W21-32:B1-12", 5);
+    }
+
+    #[test]
+    fn multi_jumps_are_counted_once_per_path() {
+        // the moves of the first ply are checked by hand: the king on 10 can capture the 4 men in a loop,
+        // going around in either direction, after which the northern player has no piece left
+        let board = Board::from_fen("B:W14,15,22,23:BK10,1").unwrap();
+        let english = board.with_rules(RuleSet::english());
+        let mut cycles: Vec<String> = divide(&english, 1)
+            .into_iter()
+            .map(|(mv, _)| mv.to_string())
+            .collect();
+        cycles.sort();
+        assert_eq!(cycles, ["10x17x26x19x10", "10x19x26x17x10"]);
+        assert_eq!(perft(&english, 2), 0);
+
+        // every stop of the loop, and every quiet move, is a move of its own when captures are optional
+        agrees_with_the_reference("B:W14,15,22,23:BK10,1", 6);
+    }
+
+    #[test]
+    fn double_jumps_branch_at_every_landing_square() {
+        let board = Board::from_fen("B:W18,19,26,27:B14,15")
+            .unwrap()
+            .with_rules(RuleSet::english());
+        // the 4 jump sequences of the first ply are checked by hand: each man jumps twice,
+        // and 14 can land on either 30 or 32 after its first jump
+        let mut jumps: Vec<String> = divide(&board, 1)
+            .into_iter()
+            .map(|(mv, _)| mv.to_string())
+            .collect();
+        jumps.sort();
+        assert_eq!(jumps, ["14x23x30", "14x23x32", "15x22x31", "15x24x31"]);
+
+        agrees_with_the_reference("B:W18,19,26,27:B14,15", 6);
+    }
+
+    #[test]
+    fn kings_and_men_jumping_through_crowded_boards() {
+        // kings capturing in several directions, men crowned in the middle of a capture, and pieces of both
+        // players able to capture at once
+        let fens = [
+            "W:WK6,K27,9,10,11:B13,14,15,K18,21,22,23",
+            "B:W5,6,7,13,14,15,K30:B1,2,K3,9,10,11,17",
+            "W:W9,10,17,18,25,26,K32:BK1,5,6,13,14,21,22",
+        ];
+
+        for fen in fens {
+            agrees_with_the_reference(fen, 5);
+        }
+    }
+
+    #[test]
+    fn divide_adds_up_to_perft() {
+        let board = Board::new().with_rules(RuleSet::english());
+        let divided = divide(&board, 5);
+
+        assert_eq!(divided.len(), 7);
+        assert_eq!(divided.iter().map(|(_, count)| count).sum::<u64>(), 7361);
+        assert!(divide(&board, 0).is_empty());
+    }
+}
@@ -0,0 +1,315 @@
+use crate::game::{
+    board::state::Board,
+    model::{action::Action, path::ActionPath, player::Player, square::Square},
+};
+
+const SEPARATOR: &str = "---------------------------------------------------";
+
+const RESET: &str = "\x1b[0m";
+const DARK: &str = "\x1b[48;5;94m";
+const LIGHT: &str = "\x1b[48;5;180m";
+const MARKED: &str = "\x1b[48;5;106m";
+const SOUTH: &str = "\x1b[1;30m";
+const NORTH: &str = "\x1b[1;97m";
+
+/// How the squares and pieces of the board are written by `Board::render`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderStyle {
+    /// The wide grid of `Display for Board`: `B` and `BK` for the southern (black) men and kings,
+    /// `W` and `WK` for the northern (white) ones
+    #[default]
+    Ascii,
+    /// A grid of unicode draughts pieces: ⛂ and ⛃ for the southern (black) men and kings, ⛀ and ⛁ for the
+    /// northern (white) ones
+    Unicode,
+    /// The 8 lines of `Board::to_compact`, one character per square without any coordinates
+    Compact,
+}
+
+/// How a board is written by `Board::render`
+/// ```rust
+/// use tiqtak::{ActionPath, Board, Player, RenderOptions, RenderStyle};
+///
+/// let mv = ActionPath::from_notation("9-13").unwrap();
+/// let board = Board::new().play(mv).unwrap();
+/// let options = RenderOptions::new()
+///     .with_style(RenderStyle::Unicode)
+///     .with_perspective(Player::North)
+///     .with_last_move(mv);
+/// println!("{}", board.render(options));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub(crate) style: RenderStyle,
+    /// Whether the squares and pieces are coloured with ANSI escape codes
+    pub(crate) colour: bool,
+    /// The player whose base is written at the bottom of the board
+    pub(crate) perspective: Player,
+    pub(crate) last_move: Option<ActionPath>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            style: RenderStyle::default(),
+            colour: false,
+            perspective: Player::South,
+            last_move: None,
+        }
+    }
+}
+
+impl RenderOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_style(mut self, style: RenderStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Colours the squares and the pieces with ANSI escape codes, for the terminals that support them
+    pub fn with_colour(mut self, colour: bool) -> Self {
+        self.colour = colour;
+        self
+    }
+
+    /// Writes the board as seen by this player, with its base at the bottom.
+    /// The southern player's view (the default) is the one of `Display for Board`
+    pub fn with_perspective(mut self, player: Player) -> Self {
+        self.perspective = player;
+        self
+    }
+
+    /// Marks the squares visited by this move. They are coloured with `with_colour`, otherwise they are
+    /// surrounded by `*` (Ascii), `[` and `]` (Unicode), or written as `*` when empty (Compact)
+    pub fn with_last_move(mut self, path: ActionPath) -> Self {
+        self.last_move = Some(path);
+        self
+    }
+
+    /// The rows and columns of the board, in the order they are written
+    fn axes(&self) -> ([u8; 8], [u8; 8]) {
+        let ascending = [0, 1, 2, 3, 4, 5, 6, 7];
+        let descending = [7, 6, 5, 4, 3, 2, 1, 0];
+
+        match self.perspective {
+            Player::South => (descending, ascending),
+            Player::North => (ascending, descending),
+        }
+    }
+
+    /// The squares (on the 32bits board) visited by the last move, moves on the 10x10 board (u50) are ignored
+    fn marked(&self) -> u32 {
+        let Some(path) = self.last_move else {
+            return 0;
+        };
+
+        path.mvs[..path.len]
+            .iter()
+            .map(|mv| Action::from(*mv))
+            .filter_map(|mv| {
+                Some((
+                    Square::at(mv.src, mv.scale).ok()?,
+                    Square::at(mv.tgt, mv.scale).ok()?,
+                ))
+            })
+            .fold(0, |marked, (src, tgt)| {
+                marked | (1 << src.to_u32()) | (1 << tgt.to_u32())
+            })
+    }
+}
+
+impl Board {
+    /// Writes the board in the style, orientation and colours of the options.
+    /// Unlike `Display for Board`, only the board itself is written
+    pub fn render(&self, options: RenderOptions) -> String {
+        let (rows, cols) = options.axes();
+        let marked = options.marked();
+        let mut out = String::new();
+
+        if options.style == RenderStyle::Ascii {
+            out.push_str(SEPARATOR);
+            out.push('\n');
+        }
+
+        for row in rows {
+            match options.style {
+                RenderStyle::Ascii => out.push_str(&format!("{} |", row + 1)),
+                RenderStyle::Unicode => out.push_str(&format!("{} ", row + 1)),
+                RenderStyle::Compact => {}
+            }
+
+            for col in cols {
+                let square = Square::from_coords(row, col).ok();
+                let piece = square.and_then(|square| self.piece(square));
+                let is_marked = square.is_some_and(|sq| (marked & (1 << sq.to_u32())) != 0);
+
+                let cell = match options.style {
+                    RenderStyle::Ascii => {
+                        let text = match piece {
+                            Some((Player::South, false)) => "B",
+                            Some((Player::South, true)) => "BK",
+                            Some((Player::North, false)) => "W",
+                            Some((Player::North, true)) => "WK",
+                            None => "",
+                        };
+                        match is_marked && !options.colour {
+                            true => format!("*{text:^3}*"),
+                            false => format!(" {text:^3} "),
+                        }
+                    }
+                    RenderStyle::Unicode => {
+                        let text = match piece {
+                            Some((Player::South, false)) => '⛂',
+                            Some((Player::South, true)) => '⛃',
+                            Some((Player::North, false)) => '⛀',
+                            Some((Player::North, true)) => '⛁',
+                            None if square.is_some() => '·',
+                            None => ' ',
+                        };
+                        match is_marked && !options.colour {
+                            true => format!("[{text}]"),
+                            false => format!(" {text} "),
+                        }
+                    }
+                    RenderStyle::Compact => match piece {
+                        Some((Player::South, false)) => "b",
+                        Some((Player::South, true)) => "B",
+                        Some((Player::North, false)) => "w",
+                        Some((Player::North, true)) => "W",
+                        None if is_marked && !options.colour => "*",
+                        None => ".",
+                    }
+                    .to_string(),
+                };
+
+                match options.colour {
+                    true => {
+                        let background = match (is_marked, square.is_some()) {
+                            (true, _) => MARKED,
+                            (false, true) => DARK,
+                            (false, false) => LIGHT,
+                        };
+                        let foreground = match piece {
+                            Some((Player::South, _)) => SOUTH,
+                            _ => NORTH,
+                        };
+                        out.push_str(&format!("{background}{foreground}{cell}{RESET}"));
+                    }
+                    false => out.push_str(&cell),
+                }
+
+                if options.style == RenderStyle::Ascii {
+                    out.push('|');
+                }
+            }
+
+            out.push('\n');
+            if options.style == RenderStyle::Ascii {
+                out.push_str(SEPARATOR);
+                out.push('\n');
+            }
+        }
+
+        let files = cols.map(|col| (b'A' + col) as char);
+        match options.style {
+            RenderStyle::Ascii => {
+                out.push_str("  |");
+                for file in files {
+                    out.push_str(&format!("  {file}  |"));
+                }
+                out.push_str(" \n");
+                out.push_str(SEPARATOR);
+                out.push('\n');
+            }
+            RenderStyle::Unicode => {
+                out.push_str("  ");
+                for file in files {
+                    out.push_str(&format!(" {} ", file.to_ascii_lowercase()));
+                }
+                out.push('\n');
+            }
+            RenderStyle::Compact => {}
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod render {
+    use crate::{
+        Board,
+        game::{
+            board::render::{RenderOptions, RenderStyle},
+            model::{path::ActionPath, player::Player},
+        },
+    };
+
+    #[test]
+    fn ascii_style_is_the_grid_of_the_display() {
+        let board = Board::new();
+        let display = board.to_string();
+
+        assert!(display.starts_with(&board.render(RenderOptions::new())));
+        assert!(!board.render(RenderOptions::new()).contains("Turn:"));
+    }
+
+    #[test]
+    fn compact_style_is_the_compact_diagram() {
+        let board = Board::from_fen("B:WK5,30:B1,14").unwrap();
+        let compact = RenderOptions::new().with_style(RenderStyle::Compact);
+
+        assert_eq!(board.render(compact), board.to_compact());
+        assert_eq!(board.render(compact).lines().count(), 8);
+    }
+
+    #[test]
+    fn unicode_style_uses_draughts_pieces() {
+        let board = Board::from_fen("B:WK5,30:B1,14").unwrap();
+        let unicode = board.render(RenderOptions::new().with_style(RenderStyle::Unicode));
+
+        assert_eq!(unicode.matches('⛂').count(), 2);
+        assert_eq!(unicode.matches('⛀').count(), 1);
+        assert_eq!(unicode.matches('⛁').count(), 1);
+        assert_eq!(unicode.lines().count(), 9);
+        assert!(unicode.ends_with(" a  b  c  d  e  f  g  h \n"));
+    }
+
+    #[test]
+    fn the_board_can_be_seen_from_the_northern_player() {
+        let board = Board::new();
+        let compact = RenderOptions::new().with_style(RenderStyle::Compact);
+        let flipped = board.render(compact.with_perspective(Player::North));
+
+        // a half turn of the board: the first line is the last one written backwards
+        let south: Vec<String> = board.render(compact).lines().map(String::from).collect();
+        let north: Vec<String> = flipped.lines().map(|l| l.chars().rev().collect()).collect();
+        assert_eq!(north, south.into_iter().rev().collect::<Vec<_>>());
+        assert!(flipped.starts_with(".b.b.b.b\n"));
+
+        let ascii = board.render(RenderOptions::new().with_perspective(Player::North));
+        assert!(ascii.contains("  |  H  |  G  |  F  |  E  |  D  |  C  |  B  |  A  | \n"));
+        assert!(ascii.contains("1 |"));
+    }
+
+    #[test]
+    fn the_last_move_is_marked() {
+        let mv = ActionPath::from_notation("9-13").unwrap();
+        let board = Board::new().play(mv).unwrap();
+        let options = RenderOptions::new().with_last_move(mv);
+
+        assert_eq!(board.render(options).matches('*').count(), 4);
+        let compact = board.render(options.with_style(RenderStyle::Compact));
+        assert_eq!(compact.matches('*').count(), 1);
+        let unicode = board.render(options.with_style(RenderStyle::Unicode));
+        assert_eq!(unicode.matches('[').count(), 2);
+
+        let coloured = board.render(options.with_colour(true));
+        assert!(!coloured.contains('*'));
+        assert_eq!(coloured.matches("\x1b[48;5;106m").count(), 2);
+        assert_eq!(coloured.matches("\x1b[0m").count(), 64);
+    }
+}
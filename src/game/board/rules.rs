@@ -0,0 +1,212 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+/// Whether a player that is able to capture is obliged to do so
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Capture {
+    /// Captures are just like every other move, and a jump sequence can stop at any point ("casual" play)
+    #[default]
+    Optional,
+    /// Standard English/American rule: when a capture is available, the player must capture
+    /// and must complete the whole jump sequence
+    Mandatory,
+}
+
+/// How far a king can move (and capture from) along a diagonal
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum KingRange {
+    /// Kings move one square at a time, and only capture an adjacent piece (English draughts)
+    #[default]
+    Short,
+    /// Kings slide any number of empty squares along a diagonal, and capture a piece anywhere on that diagonal by
+    /// landing on any of the empty squares behind it (Russian, Brazilian, Pool and International draughts)
+    Flying,
+}
+
+/// What happens to a man that reaches the opponent's base in the middle of a jump sequence
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Promotion {
+    /// The man is crowned straight away, and keeps capturing as a king (Russian draughts)
+    #[default]
+    Continue,
+    /// The man is crowned, and the promotion ends its move (English draughts)
+    Stop,
+    /// The man keeps capturing as a man, and is only crowned if it ends its move on the opponent's base
+    /// (Brazilian, Pool and International draughts)
+    PassThrough,
+}
+
+/// Which of the available capture sequences a player is allowed to play
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Majority {
+    /// Any of the capture sequences can be played
+    #[default]
+    Off,
+    /// Only the sequences capturing the most pieces can be played (International and Spanish draughts)
+    Quantity,
+    /// Italian draughts: only the sequences capturing the most pieces can be played, if there is more than one of them,
+    /// the player must capture with a king, then capture the most kings, and then capture the kings the earliest
+    Italian,
+}
+
+/// What a player must achieve to win the game
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Objective {
+    /// The player that captures all the opponent's pieces, or leaves the opponent without any move, wins
+    #[default]
+    Standard,
+    /// Antidraughts (giveaway, losing checkers): the player that loses all its pieces, or can not move, wins
+    Giveaway,
+}
+
+/// The rules a game of checkers (draughts) is played with. Every `Board` is parameterised with one of these,
+/// and the regional variants are presets of it.
+/// A custom variant can be created by overriding the fields of an existing preset
+/// ```rust
+/// use tiqtak::{Board, Capture, RuleSet};
+///
+/// let rules = RuleSet { quiet_mvs: 25, ..RuleSet::english() };
+/// let board = Board::new().with_rules(rules);
+/// assert_eq!(board.rules().capture, Capture::Mandatory);
+/// ```
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RuleSet {
+    /// Whether a player that is able to capture must do so
+    pub capture: Capture,
+    /// Whether men can capture backwards, men always move forward when they are not capturing
+    pub men_capture_backwards: bool,
+    /// How far kings can move (and capture from) along a diagonal
+    pub king_range: KingRange,
+    /// What happens to a man that reaches the opponent's base in the middle of a jump sequence
+    pub promotion: Promotion,
+    /// Whether the player must play the capture sequence that captures the most pieces
+    pub majority: Majority,
+    /// The game is a draw once any of the players makes this many quiet moves (moves without a capture) in a row
+    pub quiet_mvs: u8,
+    /// Whether men moving also resets the quiet moves, so that only king moves without a capture count towards a draw
+    pub quiet_kings_only: bool,
+    /// The game is a draw once a lone king has held out for this many moves against (at most) three kings,
+    /// `0` disables this rule
+    pub endgame_mvs: u8,
+    /// Whether the players are trying to win the game, or to lose it (antidraughts)
+    pub objective: Objective,
+}
+
+#[cfg_attr(feature = "web", wasm_bindgen)]
+impl RuleSet {
+    /// The rules this engine has always been played with: captures are optional, men only move (and capture) forward,
+    /// kings move one square at a time, and the game is a draw after 20 quiet moves
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn casual() -> Self {
+        Self {
+            capture: Capture::Optional,
+            men_capture_backwards: false,
+            king_range: KingRange::Short,
+            promotion: Promotion::Continue,
+            majority: Majority::Off,
+            quiet_mvs: 20,
+            quiet_kings_only: false,
+            endgame_mvs: 0,
+            objective: Objective::Standard,
+        }
+    }
+
+    /// English draughts (American checkers): captures are mandatory, a man's move ends when it is crowned,
+    /// and the game is a draw after 40 moves without a capture or a man moving
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn english() -> Self {
+        Self {
+            capture: Capture::Mandatory,
+            men_capture_backwards: false,
+            king_range: KingRange::Short,
+            promotion: Promotion::Stop,
+            majority: Majority::Off,
+            quiet_mvs: 40,
+            quiet_kings_only: true,
+            endgame_mvs: 0,
+            objective: Objective::Standard,
+        }
+    }
+
+    /// Russian draughts: captures are mandatory, men capture backwards, kings fly, a man crowned in the middle
+    /// of a jump sequence keeps capturing as a king, and the game is a draw after 15 king moves without a capture
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn russian() -> Self {
+        Self {
+            capture: Capture::Mandatory,
+            men_capture_backwards: true,
+            king_range: KingRange::Flying,
+            promotion: Promotion::Continue,
+            majority: Majority::Off,
+            quiet_mvs: 15,
+            quiet_kings_only: true,
+            endgame_mvs: 15,
+            objective: Objective::Standard,
+        }
+    }
+
+    /// Brazilian draughts (International draughts rules on the 8x8 board): captures are mandatory, the sequence
+    /// capturing the most pieces must be played, men capture backwards, kings fly, men are only crowned
+    /// if they end their move on the opponent's base, and a lone king against three kings draws after 16 moves
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn brazilian() -> Self {
+        Self {
+            capture: Capture::Mandatory,
+            men_capture_backwards: true,
+            king_range: KingRange::Flying,
+            promotion: Promotion::PassThrough,
+            majority: Majority::Quantity,
+            quiet_mvs: 20,
+            quiet_kings_only: true,
+            endgame_mvs: 16,
+            objective: Objective::Standard,
+        }
+    }
+
+    /// Pool checkers: captures are mandatory (but any of the capture sequences can be played), men capture backwards,
+    /// kings fly, and men are only crowned if they end their move on the opponent's base
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn pool() -> Self {
+        Self {
+            capture: Capture::Mandatory,
+            men_capture_backwards: true,
+            king_range: KingRange::Flying,
+            promotion: Promotion::PassThrough,
+            majority: Majority::Off,
+            quiet_mvs: 20,
+            quiet_kings_only: true,
+            endgame_mvs: 0,
+            objective: Objective::Standard,
+        }
+    }
+
+    /// Giveaway checkers (antidraughts): played with the English draughts rules, but the player that loses
+    /// all its pieces, or can not move, wins the game
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn giveaway() -> Self {
+        Self {
+            objective: Objective::Giveaway,
+            ..Self::english()
+        }
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::casual()
+    }
+}
@@ -3,12 +3,14 @@ use std::ops::Not;
 #[cfg(feature = "web")]
 use wasm_bindgen::prelude::*;
 
-/// Used for an action to tell whether the action belongs to a 64bits or 32bits bitboard
+/// Used for an action to tell whether the action belongs to a 64bits or 32bits bitboard (8x8 board),
+/// or to the 50 playable squares of the 10x10 (International draughts) board
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "web", wasm_bindgen)]
 pub enum Scale {
     U64 = 1,
     U32,
+    U50,
 }
 
 impl From<bool> for Scale {
@@ -23,10 +25,12 @@ impl From<bool> for Scale {
 impl Not for Scale {
     type Output = Scale;
 
+    /// The other format of the same (8x8) board, the 10x10 board only has one format
     fn not(self) -> Self::Output {
         match self {
             Self::U32 => Self::U64,
             Self::U64 => Self::U32,
+            Self::U50 => Self::U50,
         }
     }
 }
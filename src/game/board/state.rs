@@ -1,27 +1,48 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::ops::Index;
+use std::{
+    hash::{Hash, Hasher},
+    ops::Index,
+};
 #[cfg(feature = "web")]
 use wasm_bindgen::prelude::*;
 
 use crate::{
     game::{
         board::bitboard::BitBoard,
-        model::{action::Action, path::ActionPath, player::Player},
-        utils::{ApiError, Qmvs},
+        model::{
+            action::Action, movelist::MoveList, path::ActionPath, player::Player, square::Square,
+        },
+        utils::{ApiError, MoveError, Positions, Qmvs, Undo},
     },
     mcts::{
         algo::{state::State, tree_search::MCTS},
-        utils::{limit::Limit, reward::Reward, skill_level::SkillLevel, strength::Strength},
+        utils::{
+            limit::Limit,
+            reward::{DrawRule, Reward},
+            skill_level::SkillLevel,
+            strength::Strength,
+        },
     },
 };
 
-use super::scale::Scale;
+use super::{
+    render::{RenderOptions, RenderStyle},
+    rules::{Capture, Majority, Objective, RuleSet},
+    scale::Scale,
+    zobrist,
+};
+
+/// The squares of row 1 (the southern player's base) on the 32bits board
+const ROW_1: u32 = 0x0000000F;
+/// The squares of row 8 (the northern player's base) on the 32bits board
+const ROW_8: u32 = 0xF0000000;
 
 #[cfg_attr(feature = "web", wasm_bindgen)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(not(feature = "history"), derive(Copy))]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(from = "Fields"))]
 pub struct Board {
     /// white pieces and white kings
     pub north: u32,
@@ -33,8 +54,17 @@ pub struct Board {
     pub turn: Player,
     /// Quiet Moves (quite_mvs): The number of moves that's happened without a capture so far
     /// this value automatically resets to 0 for both sides after any capture.
-    /// any of the values reaching 20 would result ina  "draw"
+    /// any of the values reaching the `quiet_mvs` of the board's rules would result ina  "draw"
     pub qmvs: Qmvs,
+    /// The rules (variant) this game is played with
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) rules: RuleSet,
+    /// The positions reached since the last capture (or man moving), used to find repeated positions
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) positions: Positions,
+    /// The Zobrist hash of the position (see `Board::key`), updated by every move played on this board
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) hash: u64,
     #[cfg(feature = "history")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub(crate) prev: Vec<Self>,
@@ -57,6 +87,9 @@ impl Board {
             kings: 0,
             turn: Player::South,
             qmvs: Qmvs::default(),
+            rules: RuleSet::default(),
+            positions: Positions::default(),
+            hash: zobrist::hash(north, south, 0, Player::South),
             #[cfg(feature = "history")]
             prev: Vec::new(),
         }
@@ -73,6 +106,7 @@ impl Board {
     /// **qmvs** - meaning quiet moves (qmvs), tracks the number of quiet moves since a captures by both
     /// players, this value automatically resets to (0, 0) if any of the players captures the opponent's piece
     /// If there is no capture after atleast 20 moves (from either player), the game automatically becomes a draw  
+    /// The board is created with the default (casual) rules, see `Board::with_rules` to play a different variant
     #[cfg_attr(feature = "web", wasm_bindgen(constructor))]
     pub fn with(
         north: u32,
@@ -83,6 +117,266 @@ impl Board {
         #[cfg(feature = "history")] prev: Vec<Self>,
     ) -> Self {
         Self {
+            north,
+            south,
+            kings,
+            turn,
+            qmvs,
+            rules: RuleSet::default(),
+            positions: Positions::default(),
+            hash: zobrist::hash(north, south, kings, turn),
+            #[cfg(feature = "history")]
+            prev,
+        }
+    }
+
+    /// Creates a board from a position in the PDN FEN format i.e. the player to move (`W` or `B`), followed by the
+    /// squares (numbered 1 to 32) of the white and black pieces, with kings prefixed by `K`.
+    /// White is the northern player, and black (the first player) the southern one, and each of them is listed
+    /// exactly once (even without any piece e.g. `W:W:B5`)
+    /// ```rust
+    /// use tiqtak::{Board, Player};
+    ///
+    /// let board = Board::from_fen("W:W21,22,K30:B1,2,K9").unwrap();
+    /// assert_eq!(board.turn, Player::North);
+    /// assert_eq!(board.to_fen(), "W:W21,22,K30:B1,2,K9");
+    /// assert_eq!(Board::from_fen("B:W21-32:B1-12").unwrap(), Board::new());
+    /// ```
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn from_fen(fen: &str) -> Result<Board, ApiError> {
+        let mut fields = fen.trim().trim_end_matches('.').split(':');
+
+        let turn = match fields.next().map(str::trim) {
+            Some("W") => Player::North,
+            Some("B") => Player::South,
+            _ => return Err(ApiError::InvalidTurn),
+        };
+
+        let (mut north, mut south, mut kings) = (0u32, 0u32, 0u32);
+        let (mut whites, mut blacks) = (0, 0);
+        for field in fields.map(str::trim) {
+            let (player, pieces) = match field.split_at_checked(1) {
+                Some(("W", pieces)) => (Player::North, pieces),
+                Some(("B", pieces)) => (Player::South, pieces),
+                _ => return Err(ApiError::InvalidColor),
+            };
+
+            match player {
+                Player::North => whites += 1,
+                Player::South => blacks += 1,
+            }
+
+            // e.g. `21`, `K30`, or a range of squares `1-12`
+            for piece in pieces.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                let (king, piece) = match piece.strip_prefix('K') {
+                    Some(piece) => (true, piece),
+                    None => (false, piece),
+                };
+
+                let (from, to) = piece.split_once('-').unwrap_or((piece, piece));
+                let from = from.parse::<u8>().map_err(|_| ApiError::InvalidSquare)?;
+                let to = to.parse::<u8>().map_err(|_| ApiError::InvalidSquare)?;
+
+                for n in from..=to {
+                    let mask = 1u32 << Square::from_number(n)?.to_u32();
+                    if (north | south) & mask != 0 {
+                        return Err(ApiError::OccupiedSquare);
+                    }
+
+                    match player {
+                        Player::North => north |= mask,
+                        Player::South => south |= mask,
+                    }
+                    kings |= mask * king as u32;
+                }
+            }
+        }
+
+        if (whites, blacks) != (1, 1) {
+            return Err(ApiError::InvalidColor);
+        }
+
+        Self::try_with(
+            north,
+            south,
+            kings,
+            turn,
+            Qmvs::default(),
+            #[cfg(feature = "history")]
+            Vec::new(),
+        )
+    }
+
+    /// Writes the position on this board in the PDN FEN format e.g. `B:W21,22,K30:B1,2,K9`,
+    /// see `Board::from_fen`
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn to_fen(&self) -> String {
+        let pieces = |player: Player| {
+            let mut squares = (0..32u8)
+                .filter(|sq| self[player] & (1 << sq) != 0)
+                .map(|sq| {
+                    (
+                        Square::from_u32(sq).unwrap().number(),
+                        self.kings & (1 << sq) != 0,
+                    )
+                })
+                .collect::<Vec<_>>();
+            squares.sort();
+
+            squares
+                .into_iter()
+                .map(|(n, king)| match king {
+                    true => format!("K{n}"),
+                    false => n.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        let turn = match self.turn {
+            Player::North => 'W',
+            Player::South => 'B',
+        };
+
+        format!(
+            "{turn}:W{}:B{}",
+            pieces(Player::North),
+            pieces(Player::South)
+        )
+    }
+
+    /// Creates a board from an 8x8 diagram, either the grid printed by `Display` (with `B`, `BK`, `W` and `WK` pieces)
+    /// or the compact format of `Board::to_compact` (one character per square).
+    /// In both formats, the first row is row 8 (the northern player's base), and the `Turn:` and `Quiet moves:`
+    /// lines printed by `Display` are optional
+    /// ```rust
+    /// use tiqtak::{Board, Player};
+    ///
+    /// let board = Board::from_diagram(
+    ///     "
+    ///     .W......
+    ///     ........
+    ///     ........
+    ///     ........
+    ///     ........
+    ///     ..b.....
+    ///     ........
+    ///     ........
+    ///     Turn: North
+    ///     ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(board.to_fen(), "W:WK32:B11");
+    /// assert_eq!(board.turn, Player::North);
+    /// assert_eq!(Board::from_diagram(&board.to_string()), Ok(board));
+    /// ```
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn from_diagram(diagram: &str) -> Result<Board, ApiError> {
+        let lines = diagram.lines().map(str::trim).filter(|l| !l.is_empty());
+        let compact = !diagram.contains('|');
+
+        // the pieces (e.g. `B`, `WK`, `.`) of each row, starting from row 8
+        let rows = match compact {
+            true => lines
+                .filter(|l| l.len() == 8 && l.chars().all(|c| ".-bBwW".contains(c)))
+                .map(|l| l.chars().map(String::from).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            false => lines
+                .filter(|l| l.starts_with(|c: char| c.is_ascii_digit()) && l.contains('|'))
+                .map(|l| {
+                    l.split('|')
+                        .skip(1)
+                        .take(8)
+                        .map(|c| c.trim().to_string())
+                        .collect()
+                })
+                .collect(),
+        };
+
+        if rows.len() != 8 || rows.iter().any(|row| row.len() != 8) {
+            return Err(ApiError::InvalidDiagram);
+        }
+
+        let (mut north, mut south, mut kings) = (0u32, 0u32, 0u32);
+        for (index, row) in rows.iter().enumerate() {
+            for (col, piece) in row.iter().enumerate() {
+                let (player, king) = match (piece.as_str(), compact) {
+                    ("" | "." | "-", _) => continue,
+                    ("b", true) | ("B", false) => (Player::South, false),
+                    ("B", true) | ("BK", false) => (Player::South, true),
+                    ("w", true) | ("W", false) => (Player::North, false),
+                    ("W", true) | ("WK", false) => (Player::North, true),
+                    _ => return Err(ApiError::InvalidDiagram),
+                };
+
+                let mask = 1u32 << Square::from_coords(7 - index as u8, col as u8)?.to_u32();
+                match player {
+                    Player::North => north |= mask,
+                    Player::South => south |= mask,
+                }
+                kings |= mask * king as u32;
+            }
+        }
+
+        // e.g. `Turn: North` and `Quiet moves: Qmvs { north: 2, south: 3 }`
+        let value = |name: &str| {
+            diagram
+                .lines()
+                .find_map(|l| l.trim().strip_prefix(name))
+                .map(str::trim)
+        };
+        let turn = match value("Turn:") {
+            Some("North") => Player::North,
+            _ => Player::South,
+        };
+        let qmvs = value("Quiet moves:").unwrap_or_default();
+        let count = |player: &str| {
+            qmvs.split(player)
+                .nth(1)
+                .and_then(|rest| {
+                    rest.trim_start_matches([':', ' '])
+                        .split([',', ' ', '}'])
+                        .next()
+                })
+                .and_then(|n| n.parse::<u8>().ok())
+                .unwrap_or_default()
+        };
+        let qmvs = Qmvs {
+            north: count("north"),
+            south: count("south"),
+        };
+
+        Self::try_with(
+            north,
+            south,
+            kings,
+            turn,
+            qmvs,
+            #[cfg(feature = "history")]
+            Vec::new(),
+        )
+    }
+
+    /// Writes the pieces on this board as a compact diagram, one row per line (starting from row 8) and one character
+    /// per square: `b` and `B` for the southern (black) men and kings, `w` and `W` for the northern (white) ones,
+    /// and `.` for the empty squares. See `Board::from_diagram`
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn to_compact(&self) -> String {
+        self.render(RenderOptions::new().with_style(RenderStyle::Compact))
+    }
+
+    /// Same as `Board::with`, but the position is validated first (see `Board::validate`),
+    /// use this for the positions coming from untrusted sources
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn try_with(
+        north: u32,
+        south: u32,
+        kings: u32,
+        turn: Player,
+        qmvs: Qmvs,
+        #[cfg(feature = "history")] prev: Vec<Self>,
+    ) -> Result<Board, ApiError> {
+        let board = Self::with(
             north,
             south,
             kings,
@@ -90,7 +384,80 @@ impl Board {
             qmvs,
             #[cfg(feature = "history")]
             prev,
+        );
+
+        board.validate().map(|_| board)
+    }
+
+    /// Checks that the position on this board can happen in a game: no square holds more than one piece,
+    /// every king belongs to a player, no player has more than 12 pieces, and there is no man left
+    /// on the opponent's base (where it should have been crowned)
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn validate(&self) -> Result<(), ApiError> {
+        if self.north & self.south != 0 {
+            return Err(ApiError::OccupiedSquare);
+        }
+
+        if self.kings & !(self.north | self.south) != 0 {
+            return Err(ApiError::OrphanKing);
+        }
+
+        if self.north.count_ones() > 12 || self.south.count_ones() > 12 {
+            return Err(ApiError::TooManyPieces);
+        }
+
+        if (self.regular(Player::South) & ROW_8) | (self.regular(Player::North) & ROW_1) != 0 {
+            return Err(ApiError::UncrownedMan);
         }
+
+        Ok(())
+    }
+
+    /// Returns this board played with the provided rules (variant) instead, e.g. `RuleSet::english()`
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn with_rules(mut self, rules: RuleSet) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// The rules (variant) this board is played with
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    /// A 64bits key (hash) of the position on this board i.e. the pieces, kings, and the player to move.
+    /// Boards with the same position always have the same key. This is the Zobrist hash of the position, it is
+    /// kept up to date by the moves played on the board instead of being computed on every call.
+    /// So the key is only valid for boards built by the constructors (`Board::new`, `Board::with`,
+    /// `Board::from_fen`...) and changed by their moves (`Board::play`, `Board::make`...). Writing `north`,
+    /// `south`, `kings` or `turn` directly leaves it (and the repeated positions) out of date, build a new board
+    /// with `Board::with` instead. Debug builds check the key against the position
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn key(&self) -> u64 {
+        debug_assert_eq!(
+            self.hash,
+            zobrist::hash(self.north, self.south, self.kings, self.turn),
+            "the pieces or the turn of the board were written directly"
+        );
+        self.hash
+    }
+
+    /// The number of times the current position has been reached since the last capture (or man moving)
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn repetitions(&self) -> usize {
+        self.positions.count(self.key())
+    }
+
+    /// Whether one of the players is left with a lone king, against no more than three kings
+    fn is_endgame(&self) -> bool {
+        let (north, south) = (self.north.count_ones(), self.south.count_ones());
+        let (lone, other) = match north < south {
+            true => (north, south),
+            false => (south, north),
+        };
+
+        (self.north | self.south) == self.kings && lone == 1 && other <= 3
     }
 
     // to get the left move exclude any piece that is already on column A
@@ -107,6 +474,18 @@ impl Board {
         }
     }
 
+    /// The owner of the piece on this square, and whether that piece is a king
+    pub(crate) fn piece(&self, square: Square) -> Option<(Player, bool)> {
+        let cell = 1u32 << square.to_u32();
+        let is_king = (self.kings & cell) != 0;
+
+        match ((self.south & cell) != 0, (self.north & cell) != 0) {
+            (true, false) => Some((Player::South, is_king)),
+            (false, true) => Some((Player::North, is_king)),
+            _ => None,
+        }
+    }
+
     /// Returns the positions of the regular members for a specific color, excluding the kings on the board
     fn regular(&self, player: Player) -> u32 {
         match player {
@@ -118,120 +497,161 @@ impl Board {
     /// Checks whether the move (ActionPath) about to be played is valid based on the board's current state
     #[cfg_attr(feature = "web", wasm_bindgen)]
     pub fn is_valid(&self, path: ActionPath, turn: Player) -> bool {
-        assert!(
-            path.len > 0,
-            "Invalid Action: There must be atleast one move in an action"
-        );
+        self.legal(path, turn).is_some()
+    }
 
-        let mut action = Action::from(path[0]);
-        if action.scale == Scale::U64 {
-            action = action.transcode();
+    /// The option (move) of the player that visits the same squares as the provided path, its promotion is
+    /// decided by the board, so that paths read from a notation (e.g. `9-13`) can be played as they are
+    fn legal(&self, path: ActionPath, turn: Player) -> Option<ActionPath> {
+        self.legal_with(path, turn, self.rules)
+    }
+
+    /// The option (move) matching the path, if the board was played under these rules
+    fn legal_with(&self, path: ActionPath, turn: Player, rules: RuleSet) -> Option<ActionPath> {
+        if path.len == 0 || path.scale == Scale::U50 {
+            return None;
         }
 
-        let src_mask = 1u32 << action.src;
+        let src = Action::from(path.normalized()[0]).src;
+        if src >= 32 || (self[turn] & (1u32 << src)) == 0 {
+            return None;
+        }
+
+        // a move is only valid if it is one of the options available to the player, since a capture elsewhere
+        // on the board might be mandatory. The options are looked through as they are generated
+        let mut legal = None;
+        self.bitboard(turn, rules).generate(turn, &mut |option| {
+            if legal.is_none()
+                && option.is_capture() == path.is_capture()
+                && option.same_squares(&path)
+            {
+                legal = Some(option);
+            }
+        });
+        legal
+    }
+
+    /// Explains why the path is not one of the player's options
+    fn diagnose(&self, path: ActionPath, turn: Player) -> MoveError {
+        if path.len == 0 {
+            return MoveError::EmptyPath;
+        }
 
-        if (self[turn] & src_mask) == 0 {
-            return false;
+        let squares = path.squares();
+        if path.scale == Scale::U50 || squares.iter().any(|sq| *sq >= 32) {
+            return MoveError::IllegalMove;
         }
 
-        let result = BitBoard::new(1 << action.src, self[!turn], self[turn], self.kings).get(turn);
+        let src = 1u32 << squares[0];
+        if (self[!turn] & src) != 0 {
+            return MoveError::NotYourTurn;
+        }
+        if (self[turn] & src) == 0 {
+            return MoveError::NoPiece;
+        }
+
+        // the moving piece leaves its square, so a jump sequence may come back to it
+        let occupied = (self.north | self.south) & !src;
+        if squares[1..].iter().any(|sq| (occupied & (1 << sq)) != 0) {
+            return MoveError::TargetOccupied;
+        }
+
+        // the move would be legal if captures were optional, or if any capture sequence could be played
+        let relaxed = RuleSet {
+            capture: Capture::Optional,
+            majority: Majority::Off,
+            ..self.rules
+        };
+        if self.legal_with(path, turn, relaxed).is_some() {
+            if !path.is_capture() {
+                return MoveError::CaptureRequired;
+            }
 
-        println!("{:?}", result);
+            // a complete sequence is only rejected when another one captures more pieces
+            let majority = RuleSet {
+                majority: Majority::Off,
+                ..self.rules
+            };
+            return match self.legal_with(path, turn, majority) {
+                Some(_) => MoveError::MajorityRequired,
+                None => MoveError::IncompleteJump,
+            };
+        }
 
-        if path.scale == Scale::U64 {
-            return result.contains(&path.transcode());
-        } else {
-            return result.contains(&path);
+        // men only move towards the opponent's base, and only capture backwards if the rules allow it
+        let is_man = (self.kings & src) == 0;
+        let backwards = squares.windows(2).any(|step| {
+            let (from, to) = (
+                Square::from_u32(step[0]).unwrap(),
+                Square::from_u32(step[1]).unwrap(),
+            );
+            match turn {
+                Player::South => to.row() < from.row(),
+                Player::North => to.row() > from.row(),
+            }
+        });
+        if is_man && backwards && (!path.is_capture() || !self.rules.men_capture_backwards) {
+            return MoveError::WrongDirection;
         }
+
+        MoveError::IllegalMove
     }
 
-    /// Returns all the possible options(moves) that the selected user can play
-    #[cfg_attr(feature = "web", wasm_bindgen)]
-    pub fn options(&self, turn: Player) -> Vec<ActionPath> {
+    /// The options (moves) of the player, if the board was played under these rules
+    fn options_with(&self, turn: Player, rules: RuleSet) -> Vec<ActionPath> {
+        self.bitboard(turn, rules).get(turn)
+    }
+
+    /// The bitboard generating the moves of the player, under these rules
+    fn bitboard(&self, turn: Player, rules: RuleSet) -> BitBoard {
         let regulars = self.regular(turn);
         let kings = self.kings(turn);
         let opponent = self[!turn];
 
-        BitBoard::new(regulars | kings, opponent, 0, self.kings).get(turn)
+        BitBoard::new(regulars | kings, opponent, 0, self.kings, rules)
+    }
+
+    /// Returns all the possible options(moves) that the selected user can play
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn options(&self, turn: Player) -> Vec<ActionPath> {
+        self.options_with(turn, self.rules)
     }
 
     /// This returns a new Board state (the new board state) after the move (ActionPath) is applied to the board
     /// Please always provide only u64 format of the action for valid plays
     #[cfg_attr(feature = "web", wasm_bindgen)]
-    pub fn play(&self, action: ActionPath) -> Option<Self> {
-        if !self.is_valid(action, self.turn) {
-            println!("that's a bad move");
-            return None;
-        }
+    pub fn play(&self, action: ActionPath) -> Result<Self, MoveError> {
+        let action = self
+            .legal(action, self.turn)
+            .ok_or_else(|| self.diagnose(action, self.turn))?;
 
+        Ok(self.apply(action))
+    }
+
+    /// The board after this option (move) of the player to move is played, the option is not validated
+    pub(crate) fn apply(&self, action: ActionPath) -> Self {
         #[cfg(feature = "history")]
         let mut board = self.clone();
         #[cfg(not(feature = "history"))]
         let mut board = *self;
 
-        for mv in &action.mvs[..action.len] {
-            let mut action = Action::from(*mv);
-
-            if action.scale == Scale::U64 {
-                action = action.transcode();
-            }
-
-            let Action {
-                src,
-                tgt,
-                capture,
-                promoted,
-                ..
-            } = action;
-
-            let src_mask = 1 << src;
-            let tgt_mask = 1 << tgt;
-
-            let captured = match capture {
-                true => BitBoard::new(src_mask, self[!self.turn], self[self.turn], self.kings)
-                    .captured(src, tgt),
-                false => 0,
-            };
-
-            let is_king = (self.kings & src_mask) != 0;
-            let kings = ((self.kings & !(1 << src)) & !(1 << captured))
-                | (u32::from(is_king || promoted) << tgt);
-
-            let cp = !capture as u8;
-
-            let turn = board.turn;
-            let us = (board[turn] & !src_mask) | tgt_mask;
-            let them = board[!turn] & !((capture as u32) << tgt);
-
-            let (north, south) = match turn {
-                Player::North => (us, them),
-                Player::South => (them, us),
-            };
-
-            let mut qmvs = board.qmvs;
-            qmvs[turn] = (qmvs[turn] + 1) * cp;
-            qmvs[!turn] *= cp;
+        let irreversible = self.is_irreversible(action);
 
+        for mv in &action.mvs[..action.len] {
             #[cfg(feature = "history")]
-            let prev = {
+            {
                 let mut history = std::mem::take(&mut board.prev);
-                history.push(board);
-                history
-            };
+                history.push(board.clone());
+                board.prev = history;
+            }
 
-            board = Self::with(
-                north,
-                south,
-                kings,
-                turn,
-                qmvs,
-                #[cfg(feature = "history")]
-                prev,
-            );
+            board.step(Action::from(*mv));
         }
 
-        board.turn = !self.turn;
-        return Some(board);
+        board.pass();
+        board.positions.record(self.key(), irreversible);
+
+        board
     }
 
     #[cfg_attr(all(feature = "web", feature = "serde"), wasm_bindgen)]
@@ -286,42 +706,190 @@ impl Board {
     }
 }
 
+impl Board {
+    /// Writes all the possible options (moves) of the player into the list, without allocating.
+    /// This is the same as `Board::options`, for the searches generating moves at every position they visit
+    pub fn generate(&self, turn: Player, list: &mut MoveList) {
+        self.bitboard(turn, self.rules).fill(turn, list);
+    }
+
+    /// Whether the player has atleast one option (move), without generating all of them
+    pub fn has_moves(&self, turn: Player) -> bool {
+        self.bitboard(turn, self.rules).has_moves(turn)
+    }
+
+    /// Plays this option (move) of the player to move in place, and returns what is needed to take it back
+    /// with `Board::unmake`. Unlike `Board::play` the move is not validated, and the history (if any) is left
+    /// untouched, which makes it the cheap way of walking through the positions of a search
+    /// ```rust
+    /// use tiqtak::Board;
+    ///
+    /// let mut board = Board::new();
+    /// let mv = board.options(board.turn)[0];
+    ///
+    /// let undo = board.make(&mv);
+    /// assert_eq!(board.to_fen(), Board::new().play(mv).unwrap().to_fen());
+    /// board.unmake(undo);
+    /// assert_eq!(board, Board::new());
+    /// ```
+    pub fn make(&mut self, action: &ActionPath) -> Undo {
+        let irreversible = self.is_irreversible(*action);
+        let (north, south, kings, turn, qmvs, hash) = (
+            self.north, self.south, self.kings, self.turn, self.qmvs, self.hash,
+        );
+        let positions = self.positions.record(hash, irreversible);
+
+        for mv in &action.mvs[..action.len] {
+            self.step(Action::from(*mv));
+        }
+
+        self.pass();
+
+        Undo {
+            north,
+            south,
+            kings,
+            turn,
+            qmvs,
+            hash,
+            positions,
+        }
+    }
+
+    /// Takes back the move `Board::make` returned this undo for, it must be the last move made on this board
+    pub fn unmake(&mut self, undo: Undo) {
+        self.positions.forget(undo.positions);
+        self.north = undo.north;
+        self.south = undo.south;
+        self.kings = undo.kings;
+        self.turn = undo.turn;
+        self.qmvs = undo.qmvs;
+        self.hash = undo.hash;
+    }
+
+    /// Hands the turn over to the opponent, once all the steps of a move are played
+    fn pass(&mut self) {
+        self.turn = !self.turn;
+        self.hash ^= zobrist::TURN;
+    }
+
+    /// Whether this move can never be undone (a capture, or a man moving), the positions before it can not be
+    /// repeated anymore
+    fn is_irreversible(&self, action: ActionPath) -> bool {
+        let src = Action::from(action[0]);
+        let src = match src.scale {
+            Scale::U64 => src.transcode().src,
+            _ => src.src,
+        };
+
+        action.is_capture() || (self.kings & (1 << src)) == 0
+    }
+
+    /// Plays one step (a move, or a jump of a jump sequence) of the player to move in place, the turn is left
+    /// to the player since the next jumps of the sequence are theirs too
+    fn step(&mut self, action: Action) {
+        let action = match action.scale {
+            Scale::U64 => action.transcode(),
+            _ => action,
+        };
+
+        let Action {
+            src,
+            tgt,
+            capture,
+            promoted,
+            ..
+        } = action;
+
+        let src_mask = 1 << src;
+        let tgt_mask = 1 << tgt;
+        let turn = self.turn;
+
+        // the captured piece is looked up on the intermediate board, since the previous jumps of
+        // this path have already moved the piece (and removed the pieces it captured)
+        let captured: u32 = match capture {
+            true => {
+                let bitboard =
+                    BitBoard::new(src_mask, self[!turn], self[turn], self.kings, self.rules);
+                1 << bitboard.captured(src, tgt)
+            }
+            false => 0,
+        };
+
+        let is_king = (self.kings & src_mask) != 0;
+        self.hash ^= zobrist::piece(turn, is_king, src.into())
+            ^ zobrist::piece(turn, is_king || promoted, tgt.into());
+        if captured != 0 {
+            let square = captured.trailing_zeros();
+            self.hash ^= zobrist::piece(!turn, (self.kings & captured) != 0, square);
+        }
+
+        self.kings = (self.kings & !src_mask & !captured) | (u32::from(is_king || promoted) << tgt);
+
+        // under `quiet_kings_only`, men moving resets the quiet moves just like captures do
+        let cp = !(capture || (!is_king && self.rules.quiet_kings_only)) as u8;
+
+        let us = (self[turn] & !src_mask) | tgt_mask;
+        let them = self[!turn] & !captured;
+
+        (self.north, self.south) = match turn {
+            Player::North => (us, them),
+            Player::South => (them, us),
+        };
+
+        self.qmvs[turn] = (self.qmvs[turn] + 1) * cp;
+        self.qmvs[!turn] *= cp;
+    }
+}
+
 impl State<ActionPath, Player, ApiError> for Board {
     fn is_terminal(&self) -> bool {
         self.get_reward() != Reward::Continue
     }
 
     fn get_reward(&self) -> Reward<Player> {
+        // the player that can not play anymore, loses the game (or wins it, in antidraughts)
+        let out = |player: Player| match self.rules.objective {
+            Objective::Standard => Reward::WonBy(!player),
+            Objective::Giveaway => Reward::WonBy(player),
+        };
+
         if self.north == 0 {
-            return Reward::WonBy(Player::South);
+            return out(Player::North);
         }
 
         if self.south == 0 {
-            return Reward::WonBy(Player::North);
+            return out(Player::South);
         }
 
         let Qmvs { north: n, south: s } = self.qmvs;
-        if n >= 20 || s >= 20 {
-            return Reward::Draw;
+        let limit = self.rules.quiet_mvs;
+        if n >= limit || s >= limit {
+            return Reward::Draw(DrawRule::QuietMoves);
+        }
+
+        let limit = self.rules.endgame_mvs;
+        if limit > 0 && self.is_endgame() && (n >= limit || s >= limit) {
+            return Reward::Draw(DrawRule::Endgame);
         }
 
-        let possible_mvs = self.get_actions();
+        // threefold repetition
+        if self.repetitions() >= 3 {
+            return Reward::Draw(DrawRule::Repetition);
+        }
 
-        if possible_mvs.len() == 0 {
-            return Reward::WonBy(!self.turn);
+        if !self.has_moves(self.turn) {
+            return out(self.turn);
         }
 
         Reward::Continue
     }
 
+    /// The actions come from `get_actions`, so they are already legal and are played without validating them again
     fn apply_action(&self, action: &ActionPath) -> Result<(Self, Player), ApiError> {
-        match self.play(*action) {
-            Some(state) => {
-                let turn = state.turn;
-                return Ok((state, turn));
-            }
-            _ => Err(ApiError::IllegalMove),
-        }
+        let state = self.apply(*action);
+        let turn = state.turn;
+        Ok((state, turn))
     }
 
     fn get_current_player(&self) -> &Player {
@@ -335,6 +903,52 @@ impl State<ActionPath, Player, ApiError> for Board {
     fn get_actions(&self) -> Vec<ActionPath> {
         self.options(self.turn)
     }
+
+    fn fill_actions(&self, actions: &mut Vec<ActionPath>) {
+        actions.clear();
+        self.bitboard(self.turn, self.rules)
+            .generate(self.turn, &mut |path| actions.push(path));
+    }
+}
+
+/// Boards are hashed by their position (see `Board::key`), so boards with the same position land in the same
+/// bucket even when the rest of their state (e.g. quiet moves or history) differs
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.key());
+    }
+}
+
+/// The fields of a board read by serde, the state derived from them (its key and positions) is computed again
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct Fields {
+    north: u32,
+    south: u32,
+    kings: u32,
+    turn: Player,
+    qmvs: Qmvs,
+    #[serde(default)]
+    rules: RuleSet,
+    #[cfg(feature = "history")]
+    #[serde(default)]
+    prev: Vec<Board>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Fields> for Board {
+    fn from(fields: Fields) -> Self {
+        Self::with(
+            fields.north,
+            fields.south,
+            fields.kings,
+            fields.turn,
+            fields.qmvs,
+            #[cfg(feature = "history")]
+            fields.prev,
+        )
+        .with_rules(fields.rules)
+    }
 }
 
 impl Index<Player> for Board {
@@ -350,62 +964,650 @@ impl Index<Player> for Board {
 
 impl std::fmt::Display for Board {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let rows = 8;
-        writeln!(f, "---------------------------------------------------")?;
+        write!(f, "{}", self.render(RenderOptions::default()))?;
+
+        writeln!(f, "Turn: {:?}", self.turn)?;
+        writeln!(f, "Quiet moves: {:?}", self.qmvs)?;
+        writeln!(f, "South: {:08x}", self.south)?;
+        writeln!(f, "North: {:08x}", self.north)?;
+        writeln!(f, "Kings: {:08x}", self.kings)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod rules {
+        use crate::{
+            Action, ActionPath, Board, RuleSet, Scale,
+            algo::{DrawRule, Reward, State},
+            game::{model::player::Player, utils::Qmvs},
+        };
+
+        #[test]
+        fn quiet_moves_draw_is_decided_by_the_rule_set() {
+            let qmvs = Qmvs {
+                north: 20,
+                south: 19,
+            };
+            let board = Board::with(
+                0xfff00000,
+                0x00000fff,
+                0,
+                Player::South,
+                qmvs,
+                #[cfg(feature = "history")]
+                Vec::with_capacity(0),
+            );
+
+            assert_eq!(board.get_reward(), Reward::Draw(DrawRule::QuietMoves));
+            assert_eq!(
+                board.with_rules(RuleSet::english()).get_reward(),
+                Reward::Continue
+            );
+        }
+
+        #[test]
+        fn played_boards_keep_their_rule_set() {
+            let board = Board::new().with_rules(RuleSet::english());
+            let mv = board.options(Player::South)[0];
+
+            assert_eq!(board.play(mv).unwrap().rules(), RuleSet::english());
+        }
+
+        #[test]
+        fn giveaway_is_won_by_losing_every_piece_or_move() {
+            let board = |north, south| {
+                Board::with(
+                    north,
+                    south,
+                    0,
+                    Player::South,
+                    Qmvs::default(),
+                    #[cfg(feature = "history")]
+                    Vec::with_capacity(0),
+                )
+            };
+
+            // south has no piece left
+            let no_pieces = board(1 << 3, 0);
+            assert_eq!(no_pieces.get_reward(), Reward::WonBy(Player::North));
+            assert_eq!(
+                no_pieces.with_rules(RuleSet::giveaway()).get_reward(),
+                Reward::WonBy(Player::South)
+            );
+
+            // south's only man is on north's base, and can not move anymore
+            let no_moves = board(1 << 3, 1 << 28);
+            assert_eq!(no_moves.get_reward(), Reward::WonBy(Player::North));
+            assert_eq!(
+                no_moves.with_rules(RuleSet::giveaway()).get_reward(),
+                Reward::WonBy(Player::South)
+            );
+        }
 
-        for row in (0..8).rev() {
-            write!(f, "{} ", row + 1)?;
+        #[test]
+        fn has_moves_agrees_with_the_options() {
+            let board = |north, south, kings| {
+                Board::with(
+                    north,
+                    south,
+                    kings,
+                    Player::South,
+                    Qmvs::default(),
+                    #[cfg(feature = "history")]
+                    Vec::with_capacity(0),
+                )
+            };
+
+            let boards = || {
+                [
+                    // a man on the opponent's base, that only a king can move away from
+                    (board(1 << 3, 1 << 28, 0), false),
+                    (board(1 << 3, 1 << 28, 1 << 28), true),
+                    // a man blocked by two pieces, that can only jump over one of them
+                    (board((1 << 24) | (1 << 25), 1 << 20, 0), true),
+                    (board((1 << 24) | (1 << 25) | (1 << 29), 1 << 20, 0), false),
+                ]
+            };
 
-            for col in 0..8 {
-                let is_dark = (row + col) % 2 == 0;
-                if col == 0 {
-                    write!(f, "|")?;
+            for rules in [RuleSet::casual(), RuleSet::english(), RuleSet::russian()] {
+                for (board, has_moves) in boards() {
+                    let board = board.with_rules(rules);
+
+                    assert_eq!(board.has_moves(Player::South), has_moves);
+                    assert_eq!(!board.options(Player::South).is_empty(), has_moves);
                 }
+            }
+        }
+
+        #[test]
+        fn men_moving_resets_the_quiet_moves_of_kings_only_rules() {
+            let qmvs = Qmvs {
+                north: 10,
+                south: 10,
+            };
+            let board = Board::with(
+                1 << 26,
+                (1 << 5) | (1 << 0),
+                (1 << 26) | (1 << 5),
+                Player::South,
+                qmvs,
+                #[cfg(feature = "history")]
+                Vec::with_capacity(0),
+            );
+            let mv = ActionPath::from(Action::new(0, 4, false, false, Scale::U32).transcode());
+
+            let casual = board.play(mv).unwrap();
+            assert_eq!(casual.qmvs.south, 11);
 
-                if is_dark {
-                    let cell_in64bits = (row * rows) + col;
-                    let cell_in32bits = cell_in64bits / 2;
+            let english = board.with_rules(RuleSet::english()).play(mv).unwrap();
+            assert_eq!(english.qmvs, Qmvs::default());
+        }
+
+        #[test]
+        fn a_lone_king_against_three_kings_is_a_draw_after_the_endgame_moves() {
+            let north = (1 << 26) | (1 << 27) | (1 << 31);
+            let qmvs = Qmvs {
+                north: 16,
+                south: 16,
+            };
+            let board = Board::with(
+                north,
+                1 << 5,
+                north | (1 << 5),
+                Player::South,
+                qmvs,
+                #[cfg(feature = "history")]
+                Vec::with_capacity(0),
+            );
+
+            assert_eq!(board.get_reward(), Reward::Continue);
+            let board = board.with_rules(RuleSet::brazilian());
+            assert_eq!(board.get_reward(), Reward::Draw(DrawRule::Endgame));
+
+            let rules = RuleSet {
+                endgame_mvs: 17,
+                ..RuleSet::brazilian()
+            };
+            assert_eq!(board.with_rules(rules).get_reward(), Reward::Continue);
+        }
+    }
+
+    mod fen {
+        use crate::{
+            Board,
+            game::{
+                model::{player::Player, square::Square},
+                utils::ApiError,
+            },
+        };
+
+        /// The bit of a square (standard numbering) on the 32bits board
+        fn bit(number: u8) -> u32 {
+            1 << Square::from_number(number).unwrap().to_u32()
+        }
+
+        #[test]
+        fn positions_are_read_and_written() {
+            let board = Board::from_fen("B:W18,K27:B14,K1").unwrap();
+            assert_eq!(board.turn, Player::South);
+            assert_eq!(board.north, bit(18) | bit(27));
+            assert_eq!(board.south, bit(14) | bit(1));
+            assert_eq!(board.kings, bit(27) | bit(1));
+            assert_eq!(board.to_fen(), "B:W18,K27:BK1,14");
+
+            assert_eq!(
+                Board::new().to_fen(),
+                format!("B:W{}:B{}", join(21..=32), join(1..=12))
+            );
+            assert_eq!(Board::from_fen("W:B5:W").unwrap().to_fen(), "W:W:B5");
+        }
+
+        fn join(squares: std::ops::RangeInclusive<u8>) -> String {
+            squares.map(|n| n.to_string()).collect::<Vec<_>>().join(",")
+        }
+
+        #[test]
+        fn moves_in_the_standard_notation_can_be_played() {
+            let board = Board::from_fen("B:W5:B28").unwrap();
+            let crowned = board.play("28-32".parse().unwrap()).unwrap();
+            assert_eq!(crowned.to_fen(), "W:W5:BK32");
+
+            assert!(board.play("28-31".parse().unwrap()).is_err());
+            assert!(board.play("28x32".parse().unwrap()).is_err());
+        }
+
+        #[test]
+        fn invalid_positions_are_rejected() {
+            assert_eq!(Board::from_fen("X:W1:B2"), Err(ApiError::InvalidTurn));
+            assert_eq!(Board::from_fen("W:W1:R2"), Err(ApiError::InvalidColor));
+            assert_eq!(Board::from_fen("W:W33:B2"), Err(ApiError::InvalidSquare));
+            assert_eq!(Board::from_fen("W:W0:B2"), Err(ApiError::InvalidSquare));
+            assert_eq!(Board::from_fen("W:Wx:B2"), Err(ApiError::InvalidSquare));
+            assert_eq!(Board::from_fen("W:W1-3:B2"), Err(ApiError::OccupiedSquare));
+        }
+
+        #[test]
+        fn both_colours_are_listed_exactly_once() {
+            assert_eq!(Board::from_fen("W"), Err(ApiError::InvalidColor));
+            assert_eq!(Board::from_fen("W:W21"), Err(ApiError::InvalidColor));
+            assert_eq!(Board::from_fen("B:B1"), Err(ApiError::InvalidColor));
+            assert_eq!(Board::from_fen("W:W21:B1:W22"), Err(ApiError::InvalidColor));
+            assert_eq!(Board::from_fen("W:W21:B1:B2"), Err(ApiError::InvalidColor));
+            assert!(Board::from_fen("W:W:B1").is_ok());
+        }
+    }
+
+    mod diagram {
+        use crate::{
+            Board,
+            game::{
+                model::player::Player,
+                utils::{ApiError, Qmvs},
+            },
+        };
+
+        #[test]
+        fn printed_boards_can_be_read_back() {
+            assert_eq!(
+                Board::from_diagram(&Board::new().to_string()),
+                Ok(Board::new())
+            );
 
-                    let cell = 1 << cell_in32bits;
+            let qmvs = Qmvs {
+                north: 3,
+                south: 12,
+            };
+            let board = Board::with(
+                0x00804000,
+                0x00002dff,
+                0x00800001,
+                Player::North,
+                qmvs,
+                #[cfg(feature = "history")]
+                Vec::with_capacity(0),
+            );
+            assert_eq!(Board::from_diagram(&board.to_string()), Ok(board.clone()));
+
+            let compact = Board::from_diagram(&board.to_compact()).unwrap();
+            assert_eq!(compact.to_compact(), board.to_compact());
+            assert_eq!(
+                (compact.north, compact.south, compact.kings),
+                (board.north, board.south, board.kings)
+            );
+            assert_eq!(compact.turn, Player::South);
+        }
 
-                    // let cell = 1u32 << bit_index;
+        #[test]
+        fn compact_diagrams_have_one_character_per_square() {
+            let diagram = "
+                .w.w.w.w
+                w.w.w.w.
+                .w.w.w.w
+                ........
+                ........
+                b.b.b.b.
+                .b.b.b.b
+                b.b.b.b.
+            ";
+            assert_eq!(Board::from_diagram(diagram), Ok(Board::new()));
+            assert_eq!(
+                Board::new().to_compact(),
+                diagram.replace(' ', "").trim_start()
+            );
+        }
 
-                    let is_king = (self.kings & cell) != 0;
-                    let is_south = (self.south & cell) != 0;
-                    let is_north = (self.north & cell) != 0;
+        #[test]
+        fn invalid_diagrams_are_rejected() {
+            let rows = |first: &str| format!("{first}\n{}", "........\n".repeat(7));
 
-                    let piece = match (is_south, is_north, is_king) {
-                        (true, false, false) => "B",
-                        (true, false, true) => "BK",
-                        (false, true, false) => "W",
-                        (false, true, true) => "WK",
-                        _ => "",
-                    };
+            assert_eq!(
+                Board::from_diagram(&rows("b.......")),
+                Err(ApiError::LightSquare)
+            );
+            assert_eq!(
+                Board::from_diagram(&rows(".B......")),
+                Board::from_fen("B:W:BK32")
+            );
+            assert_eq!(
+                Board::from_diagram("........\n".repeat(7).as_str()),
+                Err(ApiError::InvalidDiagram)
+            );
 
-                    write!(f, " {:^3} |", piece)?;
-                } else {
-                    write!(f, " {:^3} |", "")?;
+            let grid = Board::new().to_string().replacen(" W ", " Q ", 1);
+            assert_eq!(Board::from_diagram(&grid), Err(ApiError::InvalidDiagram));
+        }
+    }
+
+    mod validation {
+        use crate::{
+            Board,
+            game::{
+                model::player::Player,
+                utils::{ApiError, Qmvs},
+            },
+        };
+
+        fn try_with(north: u32, south: u32, kings: u32) -> Result<Board, ApiError> {
+            Board::try_with(
+                north,
+                south,
+                kings,
+                Player::South,
+                Qmvs::default(),
+                #[cfg(feature = "history")]
+                Vec::with_capacity(0),
+            )
+        }
+
+        #[test]
+        fn possible_positions_are_accepted() {
+            assert_eq!(try_with(0xFFF00000, 0x00000FFF, 0), Ok(Board::new()));
+            assert!(try_with(0x0000000F, 0xF0000000, 0xF000000F).is_ok());
+            assert_eq!(Board::new().validate(), Ok(()));
+        }
+
+        #[test]
+        fn impossible_positions_are_rejected() {
+            assert_eq!(
+                try_with(0x00000100, 0x00000100, 0),
+                Err(ApiError::OccupiedSquare)
+            );
+            assert_eq!(
+                try_with(0x00000100, 0x00000001, 0x00000010),
+                Err(ApiError::OrphanKing)
+            );
+            assert_eq!(
+                try_with(0xFFF80000, 0x00000FFF, 0),
+                Err(ApiError::TooManyPieces)
+            );
+            assert_eq!(
+                try_with(0x00000100, 0x10000000, 0),
+                Err(ApiError::UncrownedMan)
+            );
+            assert_eq!(
+                try_with(0x00000001, 0x00000100, 0),
+                Err(ApiError::UncrownedMan)
+            );
+            assert_eq!(Board::from_fen("B:W1:B5"), Err(ApiError::UncrownedMan));
+        }
+    }
+
+    mod move_errors {
+        use crate::{
+            Board,
+            game::{
+                board::{
+                    rules::{Majority, RuleSet},
+                    scale::Scale,
+                },
+                model::path::ActionPath,
+                utils::{ApiError, MoveError},
+            },
+            mcts::algo::state::State,
+        };
+
+        fn play(board: &Board, notation: &str) -> Result<Board, MoveError> {
+            board.play(notation.parse().unwrap())
+        }
+
+        #[test]
+        fn moves_of_missing_or_opponent_pieces_are_explained() {
+            let board = Board::new();
+
+            assert_eq!(
+                board.play(ActionPath::new(Scale::U32)),
+                Err(MoveError::EmptyPath)
+            );
+            assert_eq!(play(&board, "21-17"), Err(MoveError::NotYourTurn));
+            assert_eq!(play(&board, "14-18"), Err(MoveError::NoPiece));
+            assert_eq!(play(&board, "1-5"), Err(MoveError::TargetOccupied));
+            assert!(play(&board, "9-13").is_ok());
+        }
+
+        #[test]
+        fn men_moving_backwards_are_explained() {
+            let board = Board::from_fen("B:W30:B14").unwrap();
+
+            assert_eq!(play(&board, "14-10"), Err(MoveError::WrongDirection));
+            assert_eq!(play(&board, "14-23"), Err(MoveError::IllegalMove));
+        }
+
+        #[test]
+        fn mandatory_captures_are_explained() {
+            let board = Board::from_fen("B:W18:B1,14").unwrap();
+            assert!(play(&board, "1-5").is_ok());
+
+            let english = board.with_rules(RuleSet::english());
+            assert_eq!(play(&english, "1-5"), Err(MoveError::CaptureRequired));
+            assert!(play(&english, "14x23").is_ok());
+        }
+
+        #[test]
+        fn incomplete_or_smaller_jump_sequences_are_explained() {
+            let board = Board::from_fen("B:W17,18,27:B14")
+                .unwrap()
+                .with_rules(RuleSet::english());
+            assert_eq!(play(&board, "14x23"), Err(MoveError::IncompleteJump));
+            assert!(play(&board, "14x21").is_ok());
+            assert!(play(&board, "14x23x32").is_ok());
+
+            let majority = board.with_rules(RuleSet {
+                majority: Majority::Quantity,
+                ..RuleSet::english()
+            });
+            assert_eq!(play(&majority, "14x21"), Err(MoveError::MajorityRequired));
+            assert!(play(&majority, "14x23x32").is_ok());
+        }
+
+        #[test]
+        fn api_errors_keep_why_a_move_is_illegal() {
+            let board = Board::from_fen("B:W18:B1,14")
+                .unwrap()
+                .with_rules(RuleSet::english());
+            let result = board.play("1-5".parse().unwrap()).map_err(ApiError::from);
+
+            assert_eq!(result, Err(ApiError::Move(MoveError::CaptureRequired)));
+        }
+
+        #[test]
+        fn searches_play_their_actions_without_validating_them_again() {
+            let board = Board::new();
+
+            for action in board.get_actions() {
+                let (state, turn) = board.apply_action(&action).unwrap();
+
+                assert_eq!(state, board.play(action).unwrap());
+                assert_eq!(turn, !board.turn);
+            }
+        }
+    }
+
+    mod repetition {
+        use crate::{
+            Action, ActionPath, Board, RuleSet, Scale,
+            algo::{DrawRule, Reward, State},
+            game::{model::player::Player, utils::Qmvs},
+        };
+
+        fn play(board: &Board, src: u8, tgt: u8) -> Board {
+            let mv = Action::new(src, tgt, false, false, Scale::U32).transcode();
+            board.play(ActionPath::from(mv)).unwrap()
+        }
+
+        fn kings_only() -> Board {
+            Board::with(
+                1 << 26,
+                1 << 5,
+                (1 << 26) | (1 << 5),
+                Player::South,
+                Qmvs::default(),
+                #[cfg(feature = "history")]
+                Vec::with_capacity(0),
+            )
+        }
+
+        #[test]
+        fn repeating_a_position_three_times_is_a_draw() {
+            let mut board = kings_only();
+            assert_eq!(board.repetitions(), 1);
+
+            for round in 2..=3 {
+                board = play(&board, 5, 9);
+                board = play(&board, 26, 21);
+                board = play(&board, 9, 5);
+                assert_eq!(board.get_reward(), Reward::Continue);
+                board = play(&board, 21, 26);
+
+                assert_eq!(board.key(), kings_only().key());
+                assert_eq!(board.repetitions(), round);
+            }
+
+            assert_eq!(board.get_reward(), Reward::Draw(DrawRule::Repetition));
+        }
+
+        #[test]
+        fn long_cycles_are_repeated_too() {
+            // a cycle of 16 plies, with every king going back and forth along a diagonal
+            let south = ["1-5", "5-9", "9-13", "13-17", "17-13", "13-9", "9-5", "5-1"];
+            let north = [
+                "32-28", "28-24", "24-20", "20-16", "16-20", "20-24", "24-28", "28-32",
+            ];
+            let start = Board::from_fen("B:WK32:BK1")
+                .unwrap()
+                .with_rules(RuleSet::english());
+
+            let mut board = start.clone();
+            for round in 2..=3 {
+                assert_eq!(board.get_reward(), Reward::Continue);
+                for (s, n) in south.iter().zip(north) {
+                    board = board.play(ActionPath::from_notation(s).unwrap()).unwrap();
+                    board = board.play(ActionPath::from_notation(n).unwrap()).unwrap();
                 }
+
+                assert_eq!(board.key(), start.key());
+                assert_eq!(board.repetitions(), round);
             }
 
-            writeln!(f)?;
-            writeln!(f, "---------------------------------------------------")?;
+            assert_eq!(board.get_reward(), Reward::Draw(DrawRule::Repetition));
         }
 
-        writeln!(f, "  |  A  |  B  |  C  |  D  |  E  |  F  |  G  |  H  | ")?;
-        writeln!(f, "---------------------------------------------------")?;
+        #[test]
+        fn positions_are_kept_up_to_the_longest_quiet_moves_limit() {
+            // 31 rounds of the 16 plies cycle, one round short of the quiet moves draw of the highest limit
+            let south = ["1-5", "5-9", "9-13", "13-17", "17-13", "13-9", "9-5", "5-1"];
+            let north = [
+                "32-28", "28-24", "24-20", "20-16", "16-20", "20-24", "24-28", "28-32",
+            ];
+            let rules = RuleSet {
+                quiet_mvs: u8::MAX,
+                ..RuleSet::english()
+            };
+            let mut board = Board::from_fen("B:WK32:BK1").unwrap().with_rules(rules);
 
-        writeln!(f, "Turn: {:?}", self.turn)?;
-        writeln!(f, "Quiet moves: {:?}", self.qmvs)?;
-        writeln!(f, "South: {:08x}", self.south)?;
-        writeln!(f, "North: {:08x}", self.north)?;
-        writeln!(f, "Kings: {:08x}", self.kings)?;
-        Ok(())
+            for _ in 0..31 {
+                for (s, n) in south.iter().zip(north) {
+                    board.make(&ActionPath::from_notation(s).unwrap());
+                    board.make(&ActionPath::from_notation(n).unwrap());
+                }
+            }
+
+            assert_eq!(board.qmvs.south, 248);
+            assert_eq!(board.repetitions(), 32);
+        }
+
+        #[test]
+        fn men_moving_resets_the_repetitions() {
+            let start = Board::with(
+                (1 << 26) | (1 << 31),
+                (1 << 5) | (1 << 0),
+                (1 << 26) | (1 << 5),
+                Player::South,
+                Qmvs::default(),
+                #[cfg(feature = "history")]
+                Vec::with_capacity(0),
+            );
+
+            let mut board = play(&start, 5, 9);
+            board = play(&board, 26, 21);
+            board = play(&board, 9, 5);
+            board = play(&board, 21, 26);
+            assert_eq!(board.repetitions(), 2);
+
+            board = play(&board, 0, 4);
+            assert_eq!(board.repetitions(), 1);
+            assert_ne!(board.key(), start.key());
+        }
+    }
+
+    mod make_unmake {
+        use crate::{
+            Board,
+            game::board::rules::RuleSet,
+            game::model::{movelist::MoveList, path::ActionPath},
+        };
+
+        /// The parts of the board a move changes, the history is left out since `Board::make` leaves it untouched
+        fn position(board: &Board) -> (String, u64, usize, (u8, u8)) {
+            let qmvs = (board.qmvs.north, board.qmvs.south);
+            (board.to_fen(), board.key(), board.repetitions(), qmvs)
+        }
+
+        #[test]
+        fn made_moves_are_the_played_ones_and_can_be_unmade() {
+            let fens = [
+                "B:W21-32:B1-12",
+                "B:W14,15,22,23:BK10,1",
+                "B:W17,18,27:B14",
+                "W:WK29,5:B9,10,K14,K22",
+            ];
+            let mut options = MoveList::new();
+
+            for fen in fens {
+                for rules in [RuleSet::english(), RuleSet::russian()] {
+                    let start = Board::from_fen(fen).unwrap().with_rules(rules);
+                    let mut board = Board::from_fen(fen).unwrap().with_rules(rules);
+                    board.generate(board.turn, &mut options);
+
+                    for option in &options {
+                        let undo = board.make(option);
+                        assert_eq!(position(&board), position(&start.apply(*option)), "{fen}");
+                        board.unmake(undo);
+                        assert_eq!(board, start, "{fen} {option}");
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn unmaking_restores_the_repeated_positions() {
+            let start = Board::from_fen("B:WK32:BK1,5").unwrap();
+            let cycle = ["1-6", "32-27", "6-1", "27-32"];
+
+            let mut board = start.clone();
+            let mut undos = Vec::new();
+            for notation in cycle.iter().cycle().take(40) {
+                let mv = ActionPath::from_notation(notation).unwrap();
+                undos.push(board.make(&mv));
+            }
+            assert_eq!(board.to_fen(), start.to_fen());
+            assert_eq!(board.repetitions(), 11);
+
+            // the man moving makes every earlier position impossible to reach again, until it is unmade
+            let undo = board.make(&ActionPath::from_notation("5-9").unwrap());
+            assert_eq!(board.repetitions(), 1);
+            board.unmake(undo);
+            assert_eq!(board.repetitions(), 11);
+
+            while let Some(undo) = undos.pop() {
+                board.unmake(undo);
+            }
+            assert_eq!(board, start);
+            assert_eq!(board.repetitions(), 1);
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
     #[cfg(feature = "history")]
     #[cfg(test)]
     mod undo_moves {
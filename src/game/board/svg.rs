@@ -0,0 +1,248 @@
+use std::fmt::Write;
+
+use crate::game::{
+    board::state::Board,
+    model::{action::Action, path::ActionPath, player::Player, square::Square},
+};
+
+const LIGHT: &str = "#f0d9b5";
+const DARK: &str = "#b58863";
+const HIGHLIGHT: &str = "#f7ec5a";
+const ARROW: &str = "#2e7d32";
+
+/// What is drawn on top of the board by `Board::to_svg`
+/// ```rust
+/// use tiqtak::{ActionPath, Board, Square, SvgOptions};
+///
+/// let mv = ActionPath::from_notation("9-13").unwrap();
+/// let options = SvgOptions::new()
+///     .with_highlights(&[Square::from_number(9).unwrap()])
+///     .with_arrows(mv);
+/// let svg = Board::new().to_svg(&options);
+/// assert!(svg.starts_with("<svg"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// The size (in pixels) of a square of the board
+    pub(crate) square: u32,
+    /// Whether the files (a to h) and ranks (1 to 8) are written on the edges of the board
+    pub(crate) coordinates: bool,
+    pub(crate) highlights: Vec<Square>,
+    pub(crate) arrows: Vec<ActionPath>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            square: 60,
+            coordinates: true,
+            highlights: Vec::new(),
+            arrows: Vec::new(),
+        }
+    }
+}
+
+impl SvgOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The size (in pixels) of a square, the image is 8 times as wide and as high
+    pub fn with_square_size(mut self, size: u32) -> Self {
+        self.square = size.max(1);
+        self
+    }
+
+    /// Whether the files and ranks are written on the edges of the board
+    pub fn with_coordinates(mut self, coordinates: bool) -> Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Highlights these squares, e.g. the squares of the last move
+    pub fn with_highlights(mut self, squares: &[Square]) -> Self {
+        self.highlights.extend_from_slice(squares);
+        self
+    }
+
+    /// Draws an arrow for every step of this path, moves on the 10x10 board (u50) are not drawn
+    pub fn with_arrows(mut self, path: ActionPath) -> Self {
+        self.arrows.push(path);
+        self
+    }
+}
+
+impl Board {
+    /// An SVG image of the board, drawn with the same orientation as its `Display` (row 8 at the top,
+    /// and the `a` column on the left)
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let size = options.square;
+        let width = size * 8;
+        let mut svg = String::new();
+
+        // writing into a String never fails
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{width}" viewBox="0 0 {width} {width}">"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<defs><marker id="arrowhead" viewBox="0 0 10 10" refX="5" refY="5" markerWidth="4" markerHeight="4" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="{ARROW}"/></marker></defs>"#
+        );
+
+        for row in (0..8u8).rev() {
+            for col in 0..8u8 {
+                let (x, y) = origin(row, col, size);
+                let fill = if (row + col).is_multiple_of(2) {
+                    DARK
+                } else {
+                    LIGHT
+                };
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="{size}" height="{size}" fill="{fill}"/>"#
+                );
+            }
+        }
+
+        for square in &options.highlights {
+            let (x, y) = origin(square.row(), square.col(), size);
+            let _ = writeln!(
+                svg,
+                r#"<rect class="highlight" x="{x}" y="{y}" width="{size}" height="{size}" fill="{HIGHLIGHT}" fill-opacity="0.6"/>"#
+            );
+        }
+
+        if options.coordinates {
+            let font = size / 5;
+            for i in 0..8u8 {
+                let (x, y) = origin(0, i, size);
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="{font}" font-family="sans-serif">{}</text>"#,
+                    x + size - font,
+                    y + size - font / 3,
+                    (b'a' + i) as char
+                );
+
+                let (x, y) = origin(i, 0, size);
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="{font}" font-family="sans-serif">{}</text>"#,
+                    x + font / 3,
+                    y + font,
+                    i + 1
+                );
+            }
+        }
+
+        for index in 0..32 {
+            let square = Square::from_u32(index).unwrap();
+            let Some((player, is_king)) = self.piece(square) else {
+                continue;
+            };
+
+            let (cx, cy) = centre(square, size);
+            let (fill, stroke) = match player {
+                Player::South => ("#222222", "#000000"),
+                Player::North => ("#fafafa", "#555555"),
+            };
+            let radius = size * 2 / 5;
+            let _ = writeln!(
+                svg,
+                r#"<circle class="piece" cx="{cx}" cy="{cy}" r="{radius}" fill="{fill}" stroke="{stroke}" stroke-width="2"/>"#
+            );
+
+            if is_king {
+                let _ = writeln!(
+                    svg,
+                    r#"<circle class="king" cx="{cx}" cy="{cy}" r="{}" fill="none" stroke="{HIGHLIGHT}" stroke-width="3"/>"#,
+                    radius / 2
+                );
+            }
+        }
+
+        for path in &options.arrows {
+            for mv in &path.mvs[..path.len] {
+                let Action {
+                    src, tgt, scale, ..
+                } = Action::from(*mv);
+                // the moves of the 10x10 board (u50) are not drawn
+                let (Ok(src), Ok(tgt)) = (Square::at(src, scale), Square::at(tgt, scale)) else {
+                    continue;
+                };
+
+                let (x1, y1) = centre(src, size);
+                let (x2, y2) = centre(tgt, size);
+                let _ = writeln!(
+                    svg,
+                    r#"<line class="arrow" x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{ARROW}" stroke-width="{}" stroke-opacity="0.8" marker-end="url(#arrowhead)"/>"#,
+                    (size / 10).max(1)
+                );
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// The top left corner of the square on this row and column, row 8 is drawn at the top of the image
+fn origin(row: u8, col: u8, size: u32) -> (u32, u32) {
+    (u32::from(col) * size, u32::from(7 - row) * size)
+}
+
+/// The centre of this square on the image
+fn centre(square: Square, size: u32) -> (u32, u32) {
+    let (x, y) = origin(square.row(), square.col(), size);
+    (x + size / 2, y + size / 2)
+}
+
+#[cfg(test)]
+mod svg {
+    use crate::{
+        Board,
+        game::{
+            board::svg::SvgOptions,
+            model::{path::ActionPath, square::Square},
+        },
+    };
+
+    #[test]
+    fn every_piece_and_square_is_drawn() {
+        let svg = Board::new().to_svg(&SvgOptions::new());
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 64);
+        assert_eq!(svg.matches(r#"class="piece""#).count(), 24);
+        assert_eq!(svg.matches(r#"class="king""#).count(), 0);
+        assert_eq!(svg.matches("<text").count(), 16);
+    }
+
+    #[test]
+    fn pieces_follow_the_orientation_of_the_display() {
+        let board = Board::from_fen("W:WK32:B1").unwrap();
+        let svg = board.to_svg(&SvgOptions::new().with_coordinates(false));
+
+        // square 1 (g1) is at the bottom right, and square 32 (b8) at the top left
+        assert!(svg.contains(r#"cx="390" cy="450""#));
+        assert!(svg.contains(r#"cx="90" cy="30""#));
+        assert_eq!(svg.matches(r#"class="king""#).count(), 1);
+        assert_eq!(svg.matches("<text").count(), 0);
+    }
+
+    #[test]
+    fn highlights_and_arrows_are_drawn() {
+        let path = ActionPath::from_notation("9x18x27").unwrap();
+        let options = SvgOptions::new()
+            .with_square_size(10)
+            .with_highlights(&[Square::from_number(9).unwrap()])
+            .with_arrows(path);
+        let svg = Board::new().to_svg(&options);
+
+        assert_eq!(svg.matches(r#"class="highlight""#).count(), 1);
+        assert_eq!(svg.matches(r#"class="arrow""#).count(), 2);
+        assert!(svg.contains(r#"width="80""#));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::game::model::player::Player;
+
+/// The random keys of every piece (men and kings of both players) on every square of the 32bits board, followed
+/// by the key of the northern player being the one to move. They are generated at compile time from a fixed seed,
+/// so the keys (and the hashes built with them) are the same on every build and platform
+const KEYS: [u64; 4 * 32 + 1] = keys();
+pub(crate) const TURN: u64 = KEYS[4 * 32];
+
+/// The keys drawn one after the other from the splitmix64 generator
+const fn keys() -> [u64; 4 * 32 + 1] {
+    let mut keys = [0; 4 * 32 + 1];
+    let mut state: u64 = 0;
+    let mut index = 0;
+
+    while index < keys.len() {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        keys[index] = z ^ (z >> 31);
+        index += 1;
+    }
+
+    keys
+}
+
+/// The key of this player's man (or king) on this square of the 32bits board
+pub(crate) fn piece(player: Player, king: bool, square: u32) -> u64 {
+    KEYS[(usize::from(player) * 2 + king as usize) * 32 + square as usize]
+}
+
+/// The Zobrist hash of a position i.e. the keys of all its pieces, and of the player to move, xor-ed together.
+/// Moves only change the keys of a few pieces, so the hash of the next position is found by xor-ing these keys
+/// (and the turn) in and out of this one, see `Board::step`
+pub(crate) fn hash(north: u32, south: u32, kings: u32, turn: Player) -> u64 {
+    let mut hash = (turn == Player::North) as u64 * TURN;
+
+    for (player, mut pieces) in [(Player::North, north), (Player::South, south)] {
+        while pieces != 0 {
+            let square = pieces.trailing_zeros();
+            hash ^= piece(player, (kings & (1 << square)) != 0, square);
+            pieces &= pieces - 1;
+        }
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod zobrist {
+    use std::{
+        collections::HashSet,
+        hash::{BuildHasher, RandomState},
+    };
+
+    use crate::{
+        Board,
+        game::{
+            board::{rules::RuleSet, zobrist},
+            model::{movelist::MoveList, path::ActionPath, player::Player},
+        },
+    };
+
+    fn full(board: &Board) -> u64 {
+        zobrist::hash(board.north, board.south, board.kings, board.turn)
+    }
+
+    #[test]
+    fn keys_are_distinct() {
+        let keys: HashSet<u64> = zobrist::KEYS.into_iter().collect();
+
+        assert_eq!(keys.len(), 4 * 32 + 1);
+        assert!(!keys.contains(&0));
+    }
+
+    #[test]
+    fn played_moves_update_the_hash_incrementally() {
+        // every move of a few plies deep tree, with captures, promotions and jump sequences
+        fn walk(board: &mut Board, depth: u8) {
+            assert_eq!(board.key(), full(board), "{}", board.to_fen());
+            if depth == 0 {
+                return;
+            }
+
+            let mut options = MoveList::new();
+            board.generate(board.turn, &mut options);
+            for option in &options {
+                assert_eq!(board.apply(*option).key(), full(&board.apply(*option)));
+
+                let key = board.key();
+                let undo = board.make(option);
+                walk(board, depth - 1);
+                board.unmake(undo);
+                assert_eq!(board.key(), key);
+            }
+        }
+
+        let fens = [
+            "B:W21-32:B1-12",
+            "B:W14,15,22,23:BK10,1",
+            "B:W18,19,26,27:B14,15",
+            "W:WK29,5:B9,10,K14,K22,27",
+        ];
+        for fen in fens {
+            for rules in [RuleSet::english(), RuleSet::russian()] {
+                let mut board = Board::from_fen(fen).unwrap().with_rules(rules);
+                walk(&mut board, 4);
+            }
+        }
+    }
+
+    #[test]
+    fn boards_are_hashed_by_their_position() {
+        let start = Board::new();
+        let transposed = ["9-13", "21-17", "10-14", "22-18"];
+        let swapped = ["10-14", "22-18", "9-13", "21-17"];
+
+        let play = |notations: [&str; 4]| {
+            notations.iter().fold(Board::new(), |board, notation| {
+                board
+                    .play(ActionPath::from_notation(notation).unwrap())
+                    .unwrap()
+            })
+        };
+        let (board, other) = (play(transposed), play(swapped));
+        assert_eq!(board.key(), other.key());
+
+        let hasher = RandomState::new();
+        assert_eq!(hasher.hash_one(&board), hasher.hash_one(&other));
+        assert_ne!(hasher.hash_one(&board), hasher.hash_one(&start));
+
+        // the same pieces with the other player to move
+        let turned = Board::from_fen(&format!("W{}", &board.to_fen()[1..])).unwrap();
+        assert_eq!(turned.turn, Player::North);
+        assert_eq!(turned.key(), board.key() ^ zobrist::TURN);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "written directly")]
+    fn boards_written_directly_are_caught_in_debug_builds() {
+        let mut board = Board::new();
+        board.turn = Player::North;
+        board.key();
+    }
+}
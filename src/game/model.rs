@@ -1,5 +1,7 @@
 pub mod action;
 pub mod bits;
+pub mod movelist;
 pub mod path;
 pub mod player;
 pub(crate) mod sq;
+pub mod square;
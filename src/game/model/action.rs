@@ -1,9 +1,19 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 #[cfg(feature = "web")]
 use wasm_bindgen::prelude::*;
 
-use crate::{game::board::scale::Scale, mcts::traits::Action as MctsAction};
+use crate::{
+    game::{
+        board::scale::Scale,
+        model::{
+            sq::{self, number_u50},
+            square::Square,
+        },
+        utils::ApiError,
+    },
+    mcts::traits::Action as MctsAction,
+};
 
 /**
  * 0000 0000 0000 0000
@@ -47,6 +57,7 @@ const SHIFT_TGT: u8 = 6;
 const SHIFT_CP: u8 = 12; // shift capture
 const SHIFT_P: u8 = 13; // shift promoted
 const SHIFT_BITS: u8 = 14; // shift -> bits format (e.g u64 bitboard or u32 bitboard)
+const SHIFT_U50: u8 = 15; // shift -> 10x10 board format
 
 #[cfg_attr(feature = "web", wasm_bindgen)]
 impl Action {
@@ -78,8 +89,20 @@ impl Action {
         }
     }
 
+    /// Writes this action with chess-style coordinates, e.g. `c3-d4` for a quiet move, or `c3xe5` for a capture
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn to_algebraic(&self) -> String {
+        let separator = if self.capture { 'x' } else { '-' };
+        format!(
+            "{}{separator}{}",
+            sq::to_algebraic(self.src, self.scale),
+            sq::to_algebraic(self.tgt, self.scale)
+        )
+    }
+
     /// Converts a u32 format Action to u64, and a u64 format of Action to u32
     /// NB: The term u64 or u32 refers to the actual mapping of the board.
+    /// Actions on the 10x10 board (u50) only have one format, and are returned as they are
     #[cfg_attr(feature = "web", wasm_bindgen)]
     pub fn transcode(&self) -> Self {
         let Action {
@@ -91,49 +114,75 @@ impl Action {
         } = *self;
 
         match scale {
-            Scale::U32 => {
-                let cols = 4; // number of columns per row in 32bits board
-                // whether the row of the src/tgt is an even numbered (rows are numbered from 0 to 7)
-                let (src_even, tgt_even) = ((src / cols) % 2 == 0, (tgt / cols) % 2 == 0);
-                let src = (src * 2) + !(src_even) as u8;
-                let tgt = (tgt * 2) + !(tgt_even) as u8;
-                Action::new(src, tgt, capture, promoted, Scale::U64)
+            Scale::U32 | Scale::U64 => {
+                let other = !scale;
+                // both scales describe the 8x8 board, so every square is found on the other one
+                let index = |sq| Square::at(sq, scale).and_then(|square| square.index(other));
+                Action::new(
+                    index(src).unwrap(),
+                    index(tgt).unwrap(),
+                    capture,
+                    promoted,
+                    other,
+                )
             }
-
-            Scale::U64 => Action::new(src / 2, tgt / 2, capture, promoted, Scale::U32),
+            Scale::U50 => *self,
         }
     }
 }
 
 impl MctsAction for Action {}
 
-impl Display for Action {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut value = *self;
-
-        if self.scale == Scale::U32 {
-            value = self.transcode();
+impl Action {
+    /// The number of a square of this action, in the standard numbering of the board (1 to 32, or 1 to 50)
+    pub(crate) fn number(&self, sq: u8) -> u8 {
+        match Square::at(sq, self.scale) {
+            Ok(square) => square.number(),
+            // only the squares of the 10x10 board (u50) are not on the 8x8 board
+            Err(_) => number_u50(sq),
         }
+    }
+}
 
-        let cols = ('A'..='H').into_iter().collect::<Vec<_>>();
-        let src_col = (value.src % 8) as usize;
-        let src_row = (value.src / 8) + 1;
-        let tgt_col = (value.tgt % 8) as usize;
-        let tgt_row = (value.tgt / 8) + 1;
-
+/// Writes the action in the standard (numeric) notation, e.g. `9-13` for a quiet move, or `9x18` for a capture
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let separator = if self.capture { 'x' } else { '-' };
         write!(
             f,
-            "{{src: {:?}{}, tgt: {:?}{}, capture: {:?}, promoted: {:?}, scale: {:?}}}",
-            src_row,
-            cols[src_col],
-            tgt_row,
-            cols[tgt_col],
-            value.capture,
-            value.promoted,
-            value.scale
-        )?;
-
-        Ok(())
+            "{}{separator}{}",
+            self.number(self.src),
+            self.number(self.tgt)
+        )
+    }
+}
+
+/// Reads an action of the 32 squares board in the standard (numeric) notation, e.g. `9-13` or `9x18`
+impl FromStr for Action {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (capture, (src, tgt)) = match (s.split_once('-'), s.split_once('x')) {
+            (Some(squares), None) => (false, squares),
+            (None, Some(squares)) => (true, squares),
+            _ => return Err(ApiError::InvalidNotation),
+        };
+
+        let square = |n: &str| {
+            let n = n
+                .trim()
+                .parse::<u8>()
+                .map_err(|_| ApiError::InvalidNotation)?;
+            Square::from_number(n).map(u8::from)
+        };
+
+        Ok(Self::new(
+            square(src)?,
+            square(tgt)?,
+            capture,
+            false,
+            Scale::U32,
+        ))
     }
 }
 
@@ -148,12 +197,14 @@ impl Display for Action {
 /// next 1 bit - whether this action is in u32 or u64 format for the squares
 ///     if its u64 bit should be set to 1
 ///     if its u32 bit should be set to 0
-/// last 1 bit - free for now
+/// last 1 bit - whether this action belongs to the 10x10 board (u50)
 impl From<Action> for u16 {
     fn from(value: Action) -> Self {
         let scale_bit = matches!(value.scale, Scale::U64) as u16;
+        let u50_bit = matches!(value.scale, Scale::U50) as u16;
 
-        let result = (scale_bit << SHIFT_BITS)
+        let result = (u50_bit << SHIFT_U50)
+            | (scale_bit << SHIFT_BITS)
             | (u16::from(value.promoted) << SHIFT_P)
             | (u16::from(value.capture) << SHIFT_CP)
             | (u16::from(value.tgt) << SHIFT_TGT)
@@ -170,6 +221,7 @@ impl From<u16> for Action {
         let tgt = ((value & TGT_MASK) >> SHIFT_TGT) as u8;
         let promoted = (value & (1 << SHIFT_P)) != 0;
         let scale = ((1 << SHIFT_BITS) & value) != 0;
+        let u50 = ((1 << SHIFT_U50) & value) != 0;
 
         Self {
             src,
@@ -177,7 +229,11 @@ impl From<u16> for Action {
             capture,
             promoted,
             // scale: Scale::from(is_u64),
-            scale: if scale { Scale::U64 } else { Scale::U32 },
+            scale: match (u50, scale) {
+                (true, _) => Scale::U50,
+                (false, true) => Scale::U64,
+                (false, false) => Scale::U32,
+            },
         }
     }
 }
@@ -198,7 +254,7 @@ impl From<u16> for Action {
 
 #[cfg(test)]
 mod action {
-    use crate::game::board::scale::Scale;
+    use crate::game::{board::scale::Scale, utils::ApiError};
 
     use super::Action;
 
@@ -238,6 +294,26 @@ mod action {
         assert_eq!(new_action.promoted, true);
     }
 
+    #[test]
+    fn should_read_and_write_the_standard_notation() {
+        let action = "9-13".parse::<Action>().unwrap();
+        assert_eq!(action, Action::new(11, 15, false, false, Scale::U32));
+        assert_eq!(action.to_string(), "9-13");
+        assert_eq!(action.transcode().to_string(), "9-13");
+
+        let action = "22x15".parse::<Action>().unwrap();
+        assert_eq!(action, Action::new(22, 13, true, false, Scale::U32));
+        assert_eq!(action.to_string(), "22x15");
+
+        assert_eq!(
+            Action::new(0, 5, false, false, Scale::U50).to_string(),
+            "46-41"
+        );
+        assert_eq!("9-33".parse::<Action>(), Err(ApiError::InvalidSquare));
+        assert_eq!("9x13-17".parse::<Action>(), Err(ApiError::InvalidNotation));
+        assert_eq!("9".parse::<Action>(), Err(ApiError::InvalidNotation));
+    }
+
     // #[test]
     // fn should_map_to_the_same_values() {
     //     // {src: 3C, tgt: 1E, capture: true, promoted: false, scale: U64}
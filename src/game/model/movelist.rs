@@ -0,0 +1,159 @@
+use std::ops::Deref;
+
+use crate::game::{board::scale::Scale, model::path::ActionPath};
+
+/// The moves a list holds without allocating, more than the positions of real games have. Positions with more
+/// moves do exist (e.g. flying kings with optional captures, where every stop of a jump sequence is a move of its
+/// own, can reach 400 moves), their moves spill to the heap
+const CAP: usize = 256;
+
+/// A list of moves, filled by `Board::generate` without any allocation for the positions with up to 256 moves.
+/// The same list can be reused for every position of a search, since generating moves into it clears it first
+/// ```rust
+/// use tiqtak::{Board, MoveList};
+///
+/// let board = Board::new();
+/// let mut list = MoveList::new();
+/// board.generate(board.turn, &mut list);
+///
+/// assert_eq!(list.len(), 7);
+/// assert_eq!(list.to_vec(), board.options(board.turn));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MoveList {
+    mvs: [ActionPath; CAP],
+    len: usize,
+    /// All the moves of the list, once there are more than `CAP` of them
+    spilled: Vec<ActionPath>,
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self {
+            mvs: [ActionPath::new(Scale::U32); CAP],
+            len: 0,
+            spilled: Vec::new(),
+        }
+    }
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes every move from the list
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.spilled.clear();
+    }
+
+    /// Adds a move at the end of the list, moving the whole list to the heap once it is full
+    pub(crate) fn push(&mut self, path: ActionPath) {
+        if self.len < CAP {
+            self.mvs[self.len] = path;
+            self.len += 1;
+            return;
+        }
+
+        if self.spilled.is_empty() {
+            self.spilled.extend_from_slice(&self.mvs);
+        }
+        self.spilled.push(path);
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [ActionPath];
+
+    fn deref(&self) -> &Self::Target {
+        match self.spilled.is_empty() {
+            true => &self.mvs[..self.len],
+            false => &self.spilled,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a ActionPath;
+    type IntoIter = std::slice::Iter<'a, ActionPath>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod movelist {
+    use crate::{
+        Board,
+        algo::{Reward, State},
+        game::{
+            board::{
+                perft::perft,
+                rules::{Capture, Majority, RuleSet},
+            },
+            model::{movelist::MoveList, player::Player},
+        },
+    };
+
+    #[test]
+    fn generated_moves_are_the_options_of_the_board() {
+        let majority = RuleSet {
+            majority: Majority::Quantity,
+            ..RuleSet::russian()
+        };
+        let rules = [
+            RuleSet::casual(),
+            RuleSet::english(),
+            RuleSet::russian(),
+            majority,
+        ];
+        let fens = [
+            "B:W14,15,22,23:BK10,1",
+            "B:W17,18,27:B14",
+            "W:WK29,5:B9,10,K14,K22",
+        ];
+
+        // the list is reused, since generating moves clears it first
+        let mut list = MoveList::new();
+        for fen in fens {
+            for rules in rules {
+                let board = Board::from_fen(fen).unwrap().with_rules(rules);
+                for turn in [Player::South, Player::North] {
+                    board.generate(turn, &mut list);
+                    assert_eq!(list.to_vec(), board.options(turn), "{fen}");
+                }
+            }
+        }
+
+        list.clear();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn positions_with_more_moves_than_the_capacity_spill_to_the_heap() {
+        // flying kings with optional captures, where every stop of their jump sequences is a move of its own
+        let rules = RuleSet {
+            capture: Capture::Optional,
+            ..RuleSet::russian()
+        };
+        let fen = "B:W6,7,8,16,17,18,24,25,26:BK1,K4,K5,K28,K29,K32";
+        let board = Board::from_fen(fen).unwrap().with_rules(rules);
+        assert!(board.validate().is_ok());
+
+        let mut list = MoveList::new();
+        board.generate(board.turn, &mut list);
+        assert_eq!(list.len(), 405);
+        assert_eq!(list.to_vec(), board.options(board.turn));
+        assert_eq!(perft(&board, 1), 405);
+        assert_eq!(board.get_reward(), Reward::Continue);
+        assert!(board.play(list[404]).is_ok());
+
+        // the list holds the moves of the next positions again, whatever their number
+        let next = board.play(list[0]).unwrap();
+        next.generate(Player::North, &mut list);
+        assert!(list.len() < 256);
+        assert_eq!(list.to_vec(), next.options(Player::North));
+    }
+}
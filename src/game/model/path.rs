@@ -1,14 +1,15 @@
-use std::{fmt::Display, ops::Deref};
+use std::{fmt::Display, ops::Deref, str::FromStr};
 
 #[cfg(feature = "web")]
 use wasm_bindgen::prelude::*;
 
 use crate::game::board::scale::Scale;
-use crate::game::model::action::Action;
+use crate::game::model::{action::Action, sq};
 use crate::game::utils::ApiError;
 use crate::mcts::traits::Action as MctsAction;
 
-const LEN: usize = 12;
+/// The longest jump sequence possible (on the 10x10 board)
+const LEN: usize = 20;
 
 /// A list of action the user intends to play, in a scenario where there is no jump move
 /// this would only be one move(Action)
@@ -30,9 +31,44 @@ pub struct ActionPath {
 
 impl MctsAction for ActionPath {}
 
+/// Writes the path in the standard (numeric) notation, e.g. `9-13` for a quiet move, or `9x18x27` for a jump sequence
 impl Display for ActionPath {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{:?}", &self.mvs[..self.len])
+        let Some(first) = self.first().map(|mv| Action::from(*mv)) else {
+            return Ok(());
+        };
+
+        let separator = if self.is_capture() { 'x' } else { '-' };
+        write!(f, "{}", first.number(first.src))?;
+        for mv in self.iter().map(|mv| Action::from(*mv)) {
+            write!(f, "{separator}{}", mv.number(mv.tgt))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads a path of the 32 squares board in the standard (numeric) notation, e.g. `9-13`, `9x18` or `9x18x27`
+impl FromStr for ActionPath {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let capture = s.contains('x');
+        let separator = if capture { 'x' } else { '-' };
+
+        let squares = s.split(separator).collect::<Vec<_>>();
+        // only captures can visit more than two squares
+        if squares.len() < 2 || (!capture && squares.len() > 2) || squares.len() > LEN + 1 {
+            return Err(ApiError::InvalidNotation);
+        }
+
+        let mut path = Self::new(Scale::U32);
+        for pair in squares.windows(2) {
+            let mv = format!("{}{separator}{}", pair[0], pair[1]).parse::<Action>()?;
+            path.append(mv)?;
+        }
+
+        Ok(path)
     }
 }
 
@@ -60,6 +96,104 @@ impl ActionPath {
         self.scale == Scale::U64
     }
 
+    /// Reads a path of the 32 squares board in the standard (numeric) notation, e.g. `9-13`, `9x18` or `9x18x27`
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn from_notation(notation: &str) -> Result<ActionPath, ApiError> {
+        notation.parse()
+    }
+
+    /// Writes this path with chess-style coordinates, e.g. `c3-d4` for a quiet move, or `c3xe5xg7` for a jump sequence
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn to_algebraic(&self) -> String {
+        let squares = match self.first() {
+            Some(first) => std::iter::once(Action::from(*first).src)
+                .chain(self.iter().map(|mv| Action::from(*mv).tgt))
+                .map(|square| sq::to_algebraic(square, self.scale))
+                .collect::<Vec<_>>(),
+            None => return String::new(),
+        };
+
+        squares.join(if self.is_capture() { "x" } else { "-" })
+    }
+
+    /// Reads a path written with chess-style coordinates (e.g. `c3-d4`, `c3xe5` or `c3xe5xg7`) into the actions
+    /// of the provided scale
+    /// ```rust
+    /// use tiqtak::{ActionPath, Scale};
+    ///
+    /// let path = ActionPath::from_algebraic("c3xe5xg7", Scale::U64).unwrap();
+    /// assert_eq!(path.to_algebraic(), "c3xe5xg7");
+    /// assert_eq!(path.transcode().to_algebraic(), "c3xe5xg7");
+    /// assert!(ActionPath::from_algebraic("c3-d3", Scale::U32).is_err()); // d3 is a light square
+    /// ```
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn from_algebraic(notation: &str, scale: Scale) -> Result<ActionPath, ApiError> {
+        let capture = notation.contains(['x', 'X']);
+        let squares = notation
+            .split(['x', 'X', '-'])
+            .map(|coords| sq::from_algebraic(coords, scale))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // only captures can visit more than two squares, and a path can not mix quiet moves and captures
+        let quiet = notation.contains('-');
+        if squares.len() < 2
+            || (capture && quiet)
+            || (!capture && squares.len() > 2)
+            || squares.len() > LEN + 1
+        {
+            return Err(ApiError::InvalidNotation);
+        }
+
+        let mut path = Self::new(scale);
+        for pair in squares.windows(2) {
+            path.append(Action::new(pair[0], pair[1], capture, false, scale))?;
+        }
+
+        Ok(path)
+    }
+
+    /// The squares visited by the moves of this path, starting with the square the piece leaves.
+    /// The squares of u64 paths are converted to the 32 squares board
+    pub(crate) fn squares(&self) -> Vec<u8> {
+        let path = self.normalized();
+
+        path.first()
+            .map(|mv| Action::from(*mv).src)
+            .into_iter()
+            .chain(path.iter().map(|mv| Action::from(*mv).tgt))
+            .collect()
+    }
+
+    /// Whether both paths visit the same squares (see `ActionPath::squares`), without allocating
+    pub(crate) fn same_squares(&self, other: &ActionPath) -> bool {
+        let (path, other) = (self.normalized(), other.normalized());
+        if path.scale != other.scale || path.len != other.len {
+            return false;
+        }
+
+        path.iter()
+            .zip(other.iter())
+            .enumerate()
+            .all(|(i, (a, b))| {
+                let (a, b) = (Action::from(*a), Action::from(*b));
+                a.tgt == b.tgt && (i > 0 || a.src == b.src)
+            })
+    }
+
+    /// This path with its u64 moves converted to the 32 squares board, other paths are returned as they are
+    pub(crate) fn normalized(&self) -> Self {
+        match self.scale {
+            Scale::U64 => self.transcode(),
+            Scale::U32 | Scale::U50 => *self,
+        }
+    }
+
+    /// Whether the moves on this path capture the opponent's piece(s)
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn is_capture(&self) -> bool {
+        self.len > 0 && Action::from(self.mvs[0]).capture
+    }
+
     pub(crate) fn parse(self, mv: Action) -> Result<Action, ApiError> {
         if self.len > 0 && mv.scale != self.scale {
             return Err(ApiError::IncompatibleActions);
@@ -203,3 +337,77 @@ impl TryFrom<String> for ActionPath {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod path {
+    use crate::game::{
+        board::{scale::Scale, state::Board},
+        model::{action::Action, path::ActionPath},
+        utils::ApiError,
+    };
+
+    #[test]
+    fn should_read_and_write_the_standard_notation() {
+        let path = "9x18x27".parse::<ActionPath>().unwrap();
+        assert_eq!(path.len, 2);
+        assert!(path.is_capture());
+        assert_eq!(Action::from(path[1]), "18x27".parse().unwrap());
+        assert_eq!(path.to_string(), "9x18x27");
+        assert_eq!(path.transcode().to_string(), "9x18x27");
+        assert_eq!(path.squares(), vec![11, 18, 25]);
+
+        let path = ActionPath::from_notation("11-15").unwrap();
+        assert_eq!(
+            path,
+            ActionPath::from(Action::new(9, 13, false, false, Scale::U32))
+        );
+        assert_eq!(path.to_string(), "11-15");
+
+        assert_eq!(
+            "9-13-17".parse::<ActionPath>(),
+            Err(ApiError::InvalidNotation)
+        );
+        assert_eq!(
+            "9x18-27".parse::<ActionPath>(),
+            Err(ApiError::InvalidNotation)
+        );
+        assert_eq!("9x".parse::<ActionPath>(), Err(ApiError::InvalidNotation));
+        assert_eq!("9x40".parse::<ActionPath>(), Err(ApiError::InvalidSquare));
+    }
+
+    #[test]
+    fn should_read_and_write_algebraic_coordinates() {
+        let path = ActionPath::from_algebraic("c3-d4", Scale::U64).unwrap();
+        assert_eq!(
+            path,
+            ActionPath::from(Action::new(18, 27, false, false, Scale::U64))
+        );
+        assert_eq!(path.to_algebraic(), "c3-d4");
+        assert_eq!(path.transcode().to_algebraic(), "c3-d4");
+        assert!(Board::new().play(path).is_ok());
+
+        let path = ActionPath::from_algebraic("C3XE5XG7", Scale::U32).unwrap();
+        assert_eq!(path.squares(), vec![9, 18, 27]);
+        assert_eq!(path.to_algebraic(), "c3xe5xg7");
+        assert_eq!(Action::from(path[0]).to_algebraic(), "c3xe5");
+
+        let path = ActionPath::from_algebraic("a1-b2", Scale::U50).unwrap();
+        assert_eq!(path.squares(), vec![0, 5]);
+        assert_eq!(
+            ActionPath::from_algebraic("j10-i9", Scale::U50)
+                .unwrap()
+                .squares(),
+            vec![49, 44]
+        );
+
+        let err = |notation| ActionPath::from_algebraic(notation, Scale::U64).unwrap_err();
+        assert_eq!(err("c3-d3"), ApiError::LightSquare);
+        assert_eq!(err("c3-i4"), ApiError::OffBoard);
+        assert_eq!(err("c3-d9"), ApiError::OffBoard);
+        assert_eq!(err("c0-d1"), ApiError::OffBoard);
+        assert_eq!(err("c3-d4-e5"), ApiError::InvalidNotation);
+        assert_eq!(err("c3xe5-f6"), ApiError::InvalidNotation);
+        assert_eq!(err("c3"), ApiError::InvalidNotation);
+        assert_eq!(err("3c-4d"), ApiError::InvalidNotation);
+    }
+}
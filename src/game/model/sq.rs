@@ -1,3 +1,5 @@
+use crate::game::{board::scale::Scale, model::square::Square, utils::ApiError};
+
 pub(crate) struct Sq(u8);
 
 impl TryFrom<(u8, i8)> for Sq {
@@ -31,3 +33,65 @@ impl From<Sq> for u8 {
         value.0
     }
 }
+
+/// Converts a position on the 10x10 board (0 to 49) into its number (1 to 50) in the standard numbering,
+/// square 1 is at the left hand side of the northern player's base (as seen by the southern player)
+pub(crate) fn number_u50(square: u8) -> u8 {
+    5 * (9 - square / 5) + (square % 5) + 1
+}
+
+/// The (row, column) of a square on the board of the scale, counted from `a1` (0, 0)
+pub(crate) fn coords(square: u8, scale: Scale) -> (u8, u8) {
+    match Square::at(square, scale) {
+        Ok(square) => square.coords(),
+        // only the squares of the 10x10 board (u50) are not on the 8x8 board
+        Err(_) => (square / 5, 2 * (square % 5) + (square / 5) % 2),
+    }
+}
+
+/// The square at the (row, column) of the board of the scale, only the dark squares can be played on
+pub(crate) fn from_coords(row: u8, col: u8, scale: Scale) -> Result<u8, ApiError> {
+    if scale != Scale::U50 {
+        return Square::from_coords(row, col).and_then(|square| square.index(scale));
+    }
+
+    if row >= 10 || col >= 10 {
+        return Err(ApiError::OffBoard);
+    }
+
+    if !(row + col).is_multiple_of(2) {
+        return Err(ApiError::LightSquare);
+    }
+
+    Ok(row * 5 + col / 2)
+}
+
+/// Writes a square of the board of the scale as chess-style coordinates e.g. `c3`
+pub(crate) fn to_algebraic(square: u8, scale: Scale) -> String {
+    let (row, col) = coords(square, scale);
+    format!("{}{}", (b'a' + col) as char, row + 1)
+}
+
+/// Reads chess-style coordinates (e.g. `c3`) into their (row, column), without checking that they are on the board
+pub(crate) fn parse_algebraic(coords: &str) -> Result<(u8, u8), ApiError> {
+    let coords = coords.trim().to_ascii_lowercase();
+    let mut chars = coords.chars();
+
+    let col = match chars.next() {
+        Some(c @ 'a'..='z') => c as u8 - b'a',
+        _ => return Err(ApiError::InvalidNotation),
+    };
+    let row = match chars.as_str().parse::<u8>() {
+        Ok(0) => return Err(ApiError::OffBoard),
+        Ok(row) => row - 1,
+        Err(_) => return Err(ApiError::InvalidNotation),
+    };
+
+    Ok((row, col))
+}
+
+/// Reads the chess-style coordinates (e.g. `c3`) of a square of the board of the scale
+pub(crate) fn from_algebraic(coords: &str, scale: Scale) -> Result<u8, ApiError> {
+    let (row, col) = parse_algebraic(coords)?;
+    from_coords(row, col, scale)
+}
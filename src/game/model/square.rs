@@ -0,0 +1,235 @@
+use std::{fmt::Display, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+use crate::game::{board::scale::Scale, model::sq::parse_algebraic, utils::ApiError};
+
+/// A playable (dark) square of the 8x8 board, that can be converted between all the ways of describing it:
+/// its index on the 32bits board (0 to 31), its index on the 64bits board (0 to 63), its number in the
+/// standard (PDN) numbering (1 to 32), its chess-style coordinates (`a1` to `h8`) and its (row, col).
+/// `a1` is the first square of both bitboards, and the southern player's (black) pieces start on the squares 1 to 12
+/// ```rust
+/// use tiqtak::Square;
+///
+/// let square = Square::from_algebraic("c3").unwrap();
+/// assert_eq!(square.to_u32(), 9);
+/// assert_eq!(square.to_u64(), 18);
+/// assert_eq!(square.number(), 11);
+/// assert_eq!((square.row(), square.col()), (2, 2));
+/// assert_eq!(Square::from_number(11), Ok(square));
+/// assert_eq!("11".parse::<Square>(), Ok(square));
+/// ```
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Square {
+    /// index of the square on the 32bits board
+    index: u8,
+}
+
+#[cfg_attr(feature = "web", wasm_bindgen)]
+impl Square {
+    /// The square at this index (0 to 31) of the 32bits board
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn from_u32(index: u8) -> Result<Square, ApiError> {
+        match index < 32 {
+            true => Ok(Self { index }),
+            false => Err(ApiError::OffBoard),
+        }
+    }
+
+    /// The square at this index (0 to 63) of the 64bits board, only the dark squares can be played on
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn from_u64(index: u8) -> Result<Square, ApiError> {
+        match index < 64 {
+            true => Self::from_coords(index / 8, index % 8),
+            false => Err(ApiError::OffBoard),
+        }
+    }
+
+    /// The square with this number (1 to 32) in the standard (PDN) numbering.
+    /// Square 1 is on the southern player's base, at its right hand side (`g1`)
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn from_number(number: u8) -> Result<Square, ApiError> {
+        if !(1..=32).contains(&number) {
+            return Err(ApiError::InvalidSquare);
+        }
+
+        let k = number - 1;
+        Ok(Self {
+            index: 4 * (k / 4) + 3 - (k % 4),
+        })
+    }
+
+    /// The square on this row and column (both counted from 0, at `a1`), only the dark squares can be played on
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn from_coords(row: u8, col: u8) -> Result<Square, ApiError> {
+        if row >= 8 || col >= 8 {
+            return Err(ApiError::OffBoard);
+        }
+
+        if !(row + col).is_multiple_of(2) {
+            return Err(ApiError::LightSquare);
+        }
+
+        Ok(Self {
+            index: row * 4 + col / 2,
+        })
+    }
+
+    /// The square at these chess-style coordinates e.g. `c3`
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn from_algebraic(coords: &str) -> Result<Square, ApiError> {
+        let (row, col) = parse_algebraic(coords)?;
+        Self::from_coords(row, col)
+    }
+
+    /// The index (0 to 31) of this square on the 32bits board
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn to_u32(&self) -> u8 {
+        self.index
+    }
+
+    /// The index (0 to 63) of this square on the 64bits board
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn to_u64(&self) -> u8 {
+        self.row() * 8 + self.col()
+    }
+
+    /// The number (1 to 32) of this square in the standard (PDN) numbering
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn number(&self) -> u8 {
+        4 * (self.index / 4) + 3 - (self.index % 4) + 1
+    }
+
+    /// The row (0 to 7) of this square, row 0 is the southern player's base
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn row(&self) -> u8 {
+        self.index / 4
+    }
+
+    /// The column (0 to 7) of this square, column 0 is the `a` column
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn col(&self) -> u8 {
+        2 * (self.index % 4) + self.row() % 2
+    }
+
+    /// The chess-style coordinates of this square e.g. `c3`
+    #[cfg_attr(feature = "web", wasm_bindgen)]
+    pub fn to_algebraic(&self) -> String {
+        format!("{}{}", (b'a' + self.col()) as char, self.row() + 1)
+    }
+}
+
+impl Square {
+    /// The square at this index of a board of the scale (32bits or 64bits), the index must be a valid one.
+    /// The squares of the 10x10 board (u50) are not on this board
+    pub(crate) fn at(index: u8, scale: Scale) -> Result<Self, ApiError> {
+        match scale {
+            Scale::U64 => Ok(Self { index: index / 2 }),
+            Scale::U32 => Ok(Self { index }),
+            Scale::U50 => Err(ApiError::InvalidSquare),
+        }
+    }
+
+    /// The index of this square on the board of the scale (32bits or 64bits)
+    pub(crate) fn index(&self, scale: Scale) -> Result<u8, ApiError> {
+        match scale {
+            Scale::U64 => Ok(self.to_u64()),
+            Scale::U32 => Ok(self.index),
+            Scale::U50 => Err(ApiError::InvalidSquare),
+        }
+    }
+
+    /// The (row, col) of this square
+    pub fn coords(&self) -> (u8, u8) {
+        (self.row(), self.col())
+    }
+}
+
+/// Writes the number of the square in the standard (PDN) numbering
+impl Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Reads either the number of a square (e.g. `11`), or its chess-style coordinates (e.g. `c3`)
+impl FromStr for Square {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().parse::<u8>() {
+            Ok(number) => Self::from_number(number),
+            Err(_) => Self::from_algebraic(s),
+        }
+    }
+}
+
+impl From<Square> for u8 {
+    /// The index of the square on the 32bits board
+    fn from(value: Square) -> Self {
+        value.index
+    }
+}
+
+#[cfg(test)]
+mod square {
+    use crate::{
+        Board,
+        game::{board::scale::Scale, model::square::Square, utils::ApiError},
+    };
+
+    #[test]
+    fn squares_follow_the_standard_numbering() {
+        // the first player's (South) pieces are on the squares 1 to 12
+        let south = (1..=12).fold(0u32, |acc, n| {
+            acc | 1 << Square::from_number(n).unwrap().to_u32()
+        });
+        assert_eq!(south, Board::new().south);
+        assert_eq!(Square::from_number(1).unwrap().to_algebraic(), "g1");
+        assert_eq!(Square::from_number(32).unwrap().to_algebraic(), "b8");
+        assert_eq!(Square::from_number(33), Err(ApiError::InvalidSquare));
+        assert_eq!(Square::from_number(0), Err(ApiError::InvalidSquare));
+    }
+
+    #[test]
+    fn every_conversion_round_trips() {
+        for index in 0..32 {
+            let square = Square::from_u32(index).unwrap();
+
+            assert_eq!(Square::from_u64(square.to_u64()), Ok(square));
+            assert_eq!(Square::from_number(square.number()), Ok(square));
+            assert_eq!(Square::from_algebraic(&square.to_algebraic()), Ok(square));
+            assert_eq!(Square::from_coords(square.row(), square.col()), Ok(square));
+            assert_eq!(square.to_string().parse::<Square>(), Ok(square));
+            assert_eq!(Square::at(square.to_u64(), Scale::U64), Ok(square));
+            assert_eq!(square.index(Scale::U64), Ok(square.to_u64()));
+        }
+    }
+
+    #[test]
+    fn squares_of_the_10x10_board_are_not_on_the_8x8_board() {
+        assert_eq!(Square::at(0, Scale::U50), Err(ApiError::InvalidSquare));
+        assert_eq!(Square::at(45, Scale::U50), Err(ApiError::InvalidSquare));
+        assert_eq!(
+            Square::from_u32(0).unwrap().index(Scale::U50),
+            Err(ApiError::InvalidSquare)
+        );
+    }
+
+    #[test]
+    fn invalid_squares_are_rejected() {
+        assert_eq!(Square::from_u32(32), Err(ApiError::OffBoard));
+        assert_eq!(Square::from_u64(64), Err(ApiError::OffBoard));
+        assert_eq!(Square::from_u64(1), Err(ApiError::LightSquare));
+        assert_eq!(Square::from_coords(2, 3), Err(ApiError::LightSquare));
+        assert_eq!(Square::from_coords(8, 0), Err(ApiError::OffBoard));
+        assert_eq!(Square::from_algebraic("i1"), Err(ApiError::OffBoard));
+        assert_eq!(Square::from_algebraic("a9"), Err(ApiError::OffBoard));
+        assert_eq!("1a".parse::<Square>(), Err(ApiError::InvalidNotation));
+    }
+}
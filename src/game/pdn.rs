@@ -0,0 +1,567 @@
+use std::{fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{
+    game::{
+        board::{rules::RuleSet, state::Board},
+        model::{path::ActionPath, player::Player},
+        utils::ApiError,
+    },
+    mcts::{algo::state::State, utils::reward::Reward},
+};
+
+/// The longest line of move text written to a PDN
+const LINE_LEN: usize = 80;
+/// The results a PDN game can end with, White (North) first
+const RESULTS: [&str; 8] = ["1-0", "0-1", "1/2-1/2", "2-0", "0-2", "1-1", "0-0", "*"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PdnError {
+    #[error("Malformed tag pair: {0}")]
+    Tag(String),
+    #[error("Invalid FEN: {0}")]
+    Fen(ApiError),
+    #[error("Unsupported game type: {0}")]
+    GameType(String),
+    #[error("No PDN game type matches the rules of the board")]
+    Rules,
+    #[error("Unreadable move: {0}")]
+    Notation(String),
+    #[error("Illegal move {mv} (ply {ply})")]
+    IllegalMove { ply: usize, mv: String },
+    #[error("Ambiguous move {mv} (ply {ply})")]
+    AmbiguousMove { ply: usize, mv: String },
+    #[error("Unterminated comment or variation")]
+    Unterminated,
+    #[error("No game found")]
+    Empty,
+}
+
+/// A game of checkers in Portable Draughts Notation (PDN 3.0): the tag pairs describing the game,
+/// the position it started from, and every (validated) move played on it.
+/// ```rust
+/// use tiqtak::{Board, Game};
+///
+/// let pdn = r#"
+/// [Event "Casual game"]
+/// [White "Bot"]
+/// [Black "Human"]
+///
+/// 1. 11-15 23-19 {a classic opening} 2. 8-11 (2. 9-14) 22-17 *
+/// "#;
+///
+/// let game: Game = pdn.parse().unwrap();
+/// assert_eq!(game.tag("White"), Some("Bot"));
+/// assert_eq!(game.moves().len(), 4);
+/// assert_eq!(game.board().turn, Board::new().turn);
+///
+/// let written = game.to_string();
+/// assert_eq!(written.parse::<Game>().unwrap(), game);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    tags: Vec<(String, String)>,
+    start: Board,
+    board: Board,
+    mvs: Vec<ActionPath>,
+}
+
+impl Game {
+    /// Creates a game (without any move yet) starting from the provided board. The `GameType` tag is always added,
+    /// so the board's rules must be one of the PDN game types, and the `FEN` tag whenever the position requires it
+    // the board is only `Copy` without the history feature
+    #[cfg_attr(not(feature = "history"), allow(clippy::clone_on_copy))]
+    pub fn new(board: Board) -> Result<Self, PdnError> {
+        let game_type = game_type(board.rules()).ok_or(PdnError::Rules)?;
+        let mut game = Self {
+            tags: Vec::new(),
+            start: board.clone(),
+            board,
+            mvs: Vec::new(),
+        };
+
+        game.set_tag("GameType", game_type);
+
+        let initial = Board::new();
+        let start = &game.start;
+        if (start.north, start.south, start.kings, start.turn)
+            != (initial.north, initial.south, initial.kings, initial.turn)
+        {
+            game.set_tag("FEN", &start.to_fen());
+        }
+
+        Ok(game)
+    }
+
+    /// Reads every game of a PDN file (or string)
+    pub fn parse_all(pdn: &str) -> Result<Vec<Self>, PdnError> {
+        let games = tokenize(pdn)?
+            .into_iter()
+            .map(Self::build)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match games.is_empty() {
+            true => Err(PdnError::Empty),
+            false => Ok(games),
+        }
+    }
+
+    #[cfg_attr(not(feature = "history"), allow(clippy::clone_on_copy))]
+    fn build(raw: RawGame) -> Result<Self, PdnError> {
+        let tag = |name: &str| raw.tags.iter().find(|(k, _)| k == name).map(|(_, v)| v);
+
+        // the PDN standard reads a game without a `GameType` as English draughts
+        let rules = match tag("GameType") {
+            Some(value) => rules(value)?,
+            None => RuleSet::english(),
+        };
+
+        let board = match tag("FEN") {
+            Some(value) => Board::from_fen(value).map_err(PdnError::Fen)?,
+            None => Board::new(),
+        };
+
+        let mut game = Self {
+            tags: raw.tags.clone(),
+            start: board.clone().with_rules(rules),
+            board: board.with_rules(rules),
+            mvs: Vec::new(),
+        };
+
+        for (ply, mv) in raw.mvs.iter().enumerate() {
+            let path = game.resolve(mv, ply + 1)?;
+            game.play(path).map_err(|_| PdnError::IllegalMove {
+                ply: ply + 1,
+                mv: mv.clone(),
+            })?;
+        }
+
+        if let Some(result) = raw.result
+            && tag("Result").is_none()
+        {
+            game.set_tag("Result", &result);
+        }
+
+        Ok(game)
+    }
+
+    /// Finds the move (of the player to move) described by the PDN notation e.g. `11-15`, `22x15`, or `22x15x6`
+    fn resolve(&self, mv: &str, ply: usize) -> Result<ActionPath, PdnError> {
+        let notation = mv
+            .parse::<ActionPath>()
+            .map_err(|_| PdnError::Notation(mv.to_string()))?;
+        let squares = notation.squares();
+
+        // a jump sequence can be shortened to its first and last squares e.g. `22x6` instead of `22x15x6`
+        let candidates = self
+            .board
+            .options(self.board.turn)
+            .into_iter()
+            .filter(|path| {
+                let path_squares = path.squares();
+                path.is_capture() == notation.is_capture()
+                    && path_squares.first() == squares.first()
+                    && path_squares.last() == squares.last()
+                    && (squares.len() == 2 || path_squares == squares)
+            })
+            .collect::<Vec<_>>();
+
+        match candidates.as_slice() {
+            [path] => Ok(*path),
+            [] => Err(PdnError::IllegalMove {
+                ply,
+                mv: mv.to_string(),
+            }),
+            _ => Err(PdnError::AmbiguousMove {
+                ply,
+                mv: mv.to_string(),
+            }),
+        }
+    }
+
+    /// Plays a move on the current board of the game, the move is rejected if it is illegal
+    pub fn play(&mut self, mv: ActionPath) -> Result<(), PdnError> {
+        let board = self.board.play(mv).map_err(|_| PdnError::IllegalMove {
+            ply: self.mvs.len() + 1,
+            mv: mv.to_string(),
+        })?;
+
+        self.board = board;
+        self.mvs.push(mv);
+        Ok(())
+    }
+
+    /// The value of a tag pair, e.g. `game.tag("Event")`
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Sets (or replaces) the value of a tag pair
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(k, _)| k == name) {
+            Some((_, v)) => *v = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Every tag pair of this game, in the order they were read (or set)
+    pub fn tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    /// The board this game started from
+    pub fn start(&self) -> &Board {
+        &self.start
+    }
+
+    /// The board after all the moves of this game
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// The moves played in this game, in order
+    pub fn moves(&self) -> &[ActionPath] {
+        &self.mvs
+    }
+
+    /// The result of this game, from the `Result` tag or else from the state of the board (White first)
+    pub fn result(&self) -> String {
+        if let Some(result) = self.tag("Result") {
+            return result.to_string();
+        }
+
+        match self.board.get_reward() {
+            Reward::WonBy(Player::North) => "1-0",
+            Reward::WonBy(Player::South) => "0-1",
+            Reward::Draw(_) => "1/2-1/2",
+            Reward::Continue => "*",
+        }
+        .to_string()
+    }
+}
+
+impl FromStr for Game {
+    type Err = PdnError;
+
+    /// Reads the first game of a PDN file (or string)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_all(s).map(|mut games| games.swap_remove(0))
+    }
+}
+
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let result = self.result();
+
+        for (name, value) in &self.tags {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(f, "[{name} \"{value}\"]")?;
+        }
+        if self.tag("Result").is_none() {
+            writeln!(f, "[Result \"{result}\"]")?;
+        }
+        writeln!(f)?;
+
+        let mut words = Vec::with_capacity(self.mvs.len() * 2);
+        let mut turn = self.start.turn;
+        for (index, mv) in self.mvs.iter().enumerate() {
+            let number = (index + (turn != self.start.turn) as usize) / 2 + 1;
+            match (index, turn) {
+                (0, Player::North) => words.push(format!("{number}...")),
+                (_, Player::South) => words.push(format!("{number}.")),
+                _ => {}
+            }
+
+            turn = !turn;
+            words.push(mv.to_string());
+        }
+        words.push(result);
+
+        let mut line = String::new();
+        for word in words {
+            if !line.is_empty() && line.len() + word.len() + 1 > LINE_LEN {
+                writeln!(f, "{line}")?;
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&word);
+        }
+
+        writeln!(f, "{line}")
+    }
+}
+
+/// The rules of the PDN `GameType` tag, e.g. `21` (English draughts) or `25,W,8,8,A1,0` (Russian draughts)
+fn rules(game_type: &str) -> Result<RuleSet, PdnError> {
+    let code = game_type.split(',').next().unwrap_or_default().trim();
+
+    match code {
+        "21" => Ok(RuleSet::english()),
+        "23" => Ok(RuleSet::pool()),
+        "25" => Ok(RuleSet::russian()),
+        "26" => Ok(RuleSet::brazilian()),
+        _ => Err(PdnError::GameType(game_type.to_string())),
+    }
+}
+
+/// The PDN `GameType` of the rules, if they match any of the known variants
+fn game_type(rules: RuleSet) -> Option<&'static str> {
+    [
+        (RuleSet::english(), "21"),
+        (RuleSet::pool(), "23"),
+        (RuleSet::russian(), "25"),
+        (RuleSet::brazilian(), "26"),
+    ]
+    .into_iter()
+    .find(|(preset, _)| *preset == rules)
+    .map(|(_, code)| code)
+}
+
+/// A game as read from the PDN, before any of its moves is validated
+#[derive(Debug, Default)]
+struct RawGame {
+    tags: Vec<(String, String)>,
+    mvs: Vec<String>,
+    result: Option<String>,
+}
+
+/// Splits a PDN into its games, skipping the comments, variations, move numbers and annotations
+fn tokenize(pdn: &str) -> Result<Vec<RawGame>, PdnError> {
+    let mut games = Vec::new();
+    let mut game = RawGame::default();
+    let mut chars = pdn.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '[' => {
+                // a tag after the move text starts a new game
+                if !game.mvs.is_empty() || game.result.is_some() {
+                    games.push(std::mem::take(&mut game));
+                }
+                game.tags.push(tag(&mut chars)?);
+            }
+            '{' => {
+                chars
+                    .by_ref()
+                    .find(|c| *c == '}')
+                    .ok_or(PdnError::Unterminated)?;
+            }
+            ';' => {
+                chars.by_ref().find(|c| *c == '\n');
+            }
+            '(' => {
+                let mut depth = 1;
+                while depth > 0 {
+                    match chars.next().ok_or(PdnError::Unterminated)? {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        '{' => {
+                            chars
+                                .by_ref()
+                                .find(|c| *c == '}')
+                                .ok_or(PdnError::Unterminated)?;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {
+                let mut word = String::from(c);
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !"[]{}();".contains(*c))
+                {
+                    word.push(c);
+                }
+
+                if RESULTS.contains(&word.as_str()) {
+                    game.result = Some(word);
+                    games.push(std::mem::take(&mut game));
+                    continue;
+                }
+
+                // move numbers (`1.`, `1...`), possibly glued to the move (`1.11-15`), and NAGs (`$1`)
+                let mv = word.rsplit('.').next().unwrap_or_default();
+                let mv = mv.trim_end_matches(['!', '?']);
+                if mv.is_empty() || mv.starts_with('$') {
+                    continue;
+                }
+
+                game.mvs.push(mv.to_string());
+            }
+        }
+    }
+
+    if !game.mvs.is_empty() || !game.tags.is_empty() {
+        games.push(game);
+    }
+
+    Ok(games)
+}
+
+/// Reads a tag pair e.g. `Event "Casual game"]`, the opening bracket is already consumed
+fn tag(chars: &mut impl Iterator<Item = char>) -> Result<(String, String), PdnError> {
+    let mut name = String::new();
+    let mut value = String::new();
+    let mut quoted = false;
+
+    loop {
+        match chars.next() {
+            None => return Err(PdnError::Tag(name)),
+            Some('"') if !quoted => quoted = true,
+            Some('"') => {
+                let rest = chars.by_ref().take_while(|c| *c != ']').collect::<String>();
+                if !rest.trim().is_empty() || name.is_empty() {
+                    return Err(PdnError::Tag(name));
+                }
+                return Ok((name, value));
+            }
+            Some('\\') if quoted => value.extend(chars.next()),
+            Some(c) if quoted => value.push(c),
+            Some(c) if c.is_whitespace() => {}
+            Some(']') => return Err(PdnError::Tag(name)),
+            Some(c) => name.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        board::scale::Scale,
+        model::{action::Action, square::Square},
+    };
+
+    /// A quiet move between two squares of the standard numbering
+    fn mv(src: u8, tgt: u8) -> ActionPath {
+        let (src, tgt) = (
+            Square::from_number(src).unwrap(),
+            Square::from_number(tgt).unwrap(),
+        );
+        let (src, tgt) = (src.to_u32(), tgt.to_u32());
+        ActionPath::from(Action::new(src, tgt, false, false, Scale::U32))
+    }
+
+    #[test]
+    fn reads_tags_moves_comments_and_variations() {
+        let pdn = r#"
+            [Event "Club \"Open\""]
+            [White "North"]
+            [Black "South"]
+            [Result "0-1"]
+            ; a comment until the end of the line
+            1. 11-15 {the Old Fourteenth?} 23-19 (1... 22-18 {a (reply)} 2. 15x22) 2. 8-11! 22-17 $3 0-1
+        "#;
+
+        let game = pdn.parse::<Game>().unwrap();
+        assert_eq!(game.tag("Event"), Some("Club \"Open\""));
+        assert_eq!(game.tag("Result"), Some("0-1"));
+        assert_eq!(
+            game.moves(),
+            &[mv(11, 15), mv(23, 19), mv(8, 11), mv(22, 17)]
+        );
+        assert_eq!(game.board().turn, Player::South);
+    }
+
+    #[test]
+    fn reads_captures_and_fen_positions() {
+        let pdn = r#"
+            [FEN "B:W18,K27:B14,K32"]
+            1. 14x23 27x18 *
+        "#;
+
+        let game = pdn.parse::<Game>().unwrap();
+        assert_eq!(
+            game.start().north,
+            1 << Square::from_number(18).unwrap().to_u32()
+                | 1 << Square::from_number(27).unwrap().to_u32()
+        );
+        assert_eq!(game.moves().len(), 2);
+        assert!(game.moves().iter().all(|mv| mv.is_capture()));
+        assert_eq!(
+            game.board().north,
+            1 << Square::from_number(18).unwrap().to_u32()
+        );
+        assert_eq!(
+            game.board().south,
+            1 << Square::from_number(32).unwrap().to_u32()
+        );
+        assert_eq!(game.result(), "*");
+    }
+
+    #[test]
+    fn reads_every_game_of_a_file() {
+        let pdn =
+            "[Event \"1\"]\n1. 9-13 *\n\n[Event \"2\"]\n[GameType \"21\"]\n1. 10-14 22-18 1/2-1/2";
+
+        let games = Game::parse_all(pdn).unwrap();
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[1].tag("Event"), Some("2"));
+        assert_eq!(games[1].start().rules(), RuleSet::english());
+        assert_eq!(games[1].tag("Result"), Some("1/2-1/2"));
+    }
+
+    #[test]
+    fn rejects_illegal_and_unreadable_games() {
+        assert_eq!(
+            "1. 11-17 *".parse::<Game>(),
+            Err(PdnError::IllegalMove {
+                ply: 1,
+                mv: "11-17".to_string()
+            })
+        );
+        assert_eq!(
+            "1. 11-15 {unfinished".parse::<Game>(),
+            Err(PdnError::Unterminated)
+        );
+        assert_eq!(
+            "[GameType \"20\"] *".parse::<Game>(),
+            Err(PdnError::GameType("20".to_string()))
+        );
+        assert!(matches!(
+            "[FEN \"X:W1\"] *".parse::<Game>(),
+            Err(PdnError::Fen(_))
+        ));
+        assert_eq!("".parse::<Game>(), Err(PdnError::Empty));
+    }
+
+    #[test]
+    fn played_games_are_written_back() {
+        let mut game = Game::new(Board::new().with_rules(RuleSet::english())).unwrap();
+        game.set_tag("Event", "Bots");
+        for _ in 0..6 {
+            let mv = game.board().options(game.board().turn)[0];
+            game.play(mv).unwrap();
+        }
+
+        let written = game.to_string();
+        assert!(written.starts_with("[GameType \"21\"]\n[Event \"Bots\"]\n[Result \"*\"]\n\n1. "));
+        assert_eq!(written.parse::<Game>().unwrap().moves(), game.moves());
+
+        let start = Board::from_fen("W:W21,K30:B1,2")
+            .unwrap()
+            .with_rules(RuleSet::russian());
+        let game = Game::new(start).unwrap();
+        assert_eq!(game.tag("GameType"), Some("25"));
+        assert_eq!(game.tag("FEN"), Some("W:W21,K30:B1,2"));
+    }
+
+    #[test]
+    fn games_without_a_game_type_are_english() {
+        let game = "1. 11-15 23-19 *".parse::<Game>().unwrap();
+        assert_eq!(game.start().rules(), RuleSet::english());
+
+        // the casual rules match none of the PDN game types, so their games can not be written
+        assert_eq!(Game::new(Board::new()), Err(PdnError::Rules));
+        assert_eq!(
+            Game::new(Board::new().with_rules(RuleSet::giveaway())),
+            Err(PdnError::Rules)
+        );
+    }
+}
@@ -10,7 +10,8 @@ use crate::mcts::traits::MCTSError;
 
 use super::model::player::Player;
 
-#[cfg_attr(feature = "web", wasm_bindgen)]
+/// The errors of the API. They reach JavaScript as `Error`s holding their message, since the reasons of
+/// `ApiError::Move` can not be carried by a `wasm_bindgen` enum
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum ApiError {
     #[error("Illegal move")]
@@ -19,10 +20,72 @@ pub enum ApiError {
     IncompatibleActions,
     #[error("Too many actions")]
     TooManyActions,
+    #[error("The player to move must be either W (white) or B (black)")]
+    InvalidTurn,
+    #[error("The pieces must be listed after either W (white) or B (black)")]
+    InvalidColor,
+    #[error("Squares must be numbered from 1 to 32")]
+    InvalidSquare,
+    #[error("A square can only hold one piece")]
+    OccupiedSquare,
+    #[error("Moves must be written as 9-13 (quiet move), or 9x18 and 9x18x27 (captures)")]
+    InvalidNotation,
+    #[error("Pieces can only be played on the dark squares of the board")]
+    LightSquare,
+    #[error("The coordinates are outside of the board")]
+    OffBoard,
+    #[error("A diagram must have 8 rows of 8 squares, holding either B, BK, W or WK pieces")]
+    InvalidDiagram,
+    #[error("Every king must be one of the players' pieces")]
+    OrphanKing,
+    #[error("A player can not have more than 12 pieces")]
+    TooManyPieces,
+    #[error("A man on the opponent's base must be crowned")]
+    UncrownedMan,
+    /// A move that can not be played, with the reason why
+    #[error("{0}")]
+    Move(MoveError),
 }
 
 impl MCTSError for ApiError {}
 
+#[cfg(feature = "web")]
+impl From<ApiError> for JsValue {
+    fn from(error: ApiError) -> Self {
+        JsError::new(&error.to_string()).into()
+    }
+}
+
+/// Why a move (ActionPath) can not be played on the board
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum MoveError {
+    #[error("A move must have at least one step")]
+    EmptyPath,
+    #[error("It is not your turn, the piece on the source square belongs to your opponent")]
+    NotYourTurn,
+    #[error("There is no piece of yours on the source square")]
+    NoPiece,
+    #[error("The target square is occupied")]
+    TargetOccupied,
+    #[error("A man can not move in that direction")]
+    WrongDirection,
+    #[error("A capture is available elsewhere on the board, and must be played")]
+    CaptureRequired,
+    #[error("The jump sequence is incomplete, the piece must keep capturing")]
+    IncompleteJump,
+    #[error("Another capture sequence takes more pieces, and must be played")]
+    MajorityRequired,
+    #[error("Illegal move")]
+    IllegalMove,
+}
+
+impl From<MoveError> for ApiError {
+    fn from(reason: MoveError) -> Self {
+        ApiError::Move(reason)
+    }
+}
+
 /// Number of quiet moves per player
 #[cfg_attr(feature = "web", wasm_bindgen)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
@@ -66,3 +129,96 @@ impl IndexMut<Player> for Qmvs {
         }
     }
 }
+
+/// The positions (keys) reached before the current one, since the last irreversible move (a capture, or a man
+/// moving) of the game. An irreversible move makes every earlier position impossible to reach again, so only these
+/// positions can ever be repeated.
+/// The keys are kept in a ring of `CAP` keys, so the board stays `Copy`. A run of reversible moves is ended by the
+/// quiet moves rule after at most `2 * u8::MAX` plies, so the positions that can be repeated always fit. The keys
+/// before the last irreversible move are only kept for `Positions::forget`, until the ring writes over them
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Positions {
+    keys: [u64; Positions::CAP],
+    /// The number of keys recorded so far, only the last `CAP` of them are kept
+    len: usize,
+    /// The first of the keys that can still be repeated
+    start: usize,
+}
+
+impl Positions {
+    const CAP: usize = 512;
+
+    /// Records the position left by a move, forgetting all the previous positions if the move was irreversible.
+    /// Returns what is needed to take the record back (see `Positions::forget`)
+    pub(crate) fn record(&mut self, key: u64, irreversible: bool) -> Recorded {
+        let recorded = Recorded {
+            len: self.len,
+            start: self.start,
+        };
+
+        match irreversible {
+            true => self.start = self.len,
+            false => {
+                self.keys[self.len % Self::CAP] = key;
+                self.len += 1;
+            }
+        }
+
+        recorded
+    }
+
+    /// Takes back the last record, the positions are left as they were before it
+    pub(crate) fn forget(&mut self, recorded: Recorded) {
+        self.len = recorded.len;
+        self.start = recorded.start;
+    }
+
+    /// The keys that can still be repeated, oldest first
+    fn repeatable(&self) -> impl Iterator<Item = u64> + '_ {
+        let start = self.start.max(self.len.saturating_sub(Self::CAP));
+        (start..self.len).map(|i| self.keys[i % Self::CAP])
+    }
+
+    /// The number of times the current position (with this key) has been reached
+    pub(crate) fn count(&self, key: u64) -> usize {
+        1 + self.repeatable().filter(|k| *k == key).count()
+    }
+}
+
+impl Default for Positions {
+    fn default() -> Self {
+        Self {
+            keys: [0; Self::CAP],
+            len: 0,
+            start: 0,
+        }
+    }
+}
+
+/// Only the positions that can still be repeated are compared
+impl PartialEq for Positions {
+    fn eq(&self, other: &Self) -> bool {
+        self.repeatable().eq(other.repeatable())
+    }
+}
+
+impl Eq for Positions {}
+
+/// What a record of `Positions` changed: the number of positions, and the first one that could be repeated before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Recorded {
+    len: usize,
+    start: usize,
+}
+
+/// What `Board::make` changed on a board, handed back to `Board::unmake` to take the move back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undo {
+    pub(crate) north: u32,
+    pub(crate) south: u32,
+    pub(crate) kings: u32,
+    pub(crate) turn: Player,
+    pub(crate) qmvs: Qmvs,
+    pub(crate) hash: u64,
+    pub(crate) positions: Recorded,
+}
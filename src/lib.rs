@@ -16,7 +16,7 @@
 //! let is_valid = board.is_valid(mv, board.turn);
 //! assert!(possible_mvs.contains(&mv));
 //! assert!(is_valid);
-//! let new_board = board.play(mv).unwrap(); // return None if the mv is invalid
+//! let new_board = board.play(mv).unwrap(); // returns a MoveError if the mv is invalid
 //! println!("{board}");
 //! assert_ne!(board, new_board);
 //! ```
@@ -24,18 +24,26 @@
 mod game;
 mod mcts;
 
+pub use game::board::international::InternationalBoard;
+pub use game::board::perft::{divide, perft};
+pub use game::board::render::{RenderOptions, RenderStyle};
+pub use game::board::rules::{Capture, KingRange, Majority, Objective, Promotion, RuleSet};
 pub use game::board::scale::Scale;
 pub use game::board::state::Board;
+pub use game::board::svg::SvgOptions;
 pub use game::model::action::Action;
+pub use game::model::movelist::MoveList;
 pub use game::model::path::ActionPath;
 pub use game::model::player::Player;
-pub use game::utils::Qmvs;
+pub use game::model::square::Square;
+pub use game::pdn::{Game, PdnError};
+pub use game::utils::{MoveError, Qmvs, Undo};
 
 pub mod algo {
     use crate::mcts;
     pub use mcts::algo::{state::State, tree_search::MCTS};
     pub use mcts::utils::limit::Limit;
-    pub use mcts::utils::reward::Reward;
+    pub use mcts::utils::reward::{DrawRule, Reward};
     pub use mcts::utils::skill_level::SkillLevel;
     pub use mcts::utils::strength::Strength;
 }
@@ -106,7 +106,7 @@ where
                 // return Some(-1_f64);
                 return Some(cost_of_losing);
             }
-            Reward::Draw => Some(0.5_f64),
+            Reward::Draw(_) => Some(0.5_f64),
             Reward::Continue => None,
         }
     }
@@ -127,6 +127,10 @@ where
         self.state.get_actions()
     }
 
+    pub fn fill_actions(&self, actions: &mut Vec<A>) {
+        self.state.fill_actions(actions)
+    }
+
     fn get_unexpanded_actions(&self) -> Vec<A> {
         let actions = self.get_actions();
 
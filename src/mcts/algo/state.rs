@@ -31,6 +31,13 @@ where
     /// Returns all the possible moves for the current state of the "BOARD" (e.g get_moves)
     fn get_actions(&self) -> Vec<A>;
 
+    /// Writes all the possible moves into `actions` (just like `get_actions`), so that the same list can be
+    /// reused at every ply of a simulation
+    fn fill_actions(&self, actions: &mut Vec<A>) {
+        actions.clear();
+        actions.extend(self.get_actions());
+    }
+
     fn get_current_player(&self) -> &P;
 
     fn view(&self) -> String;
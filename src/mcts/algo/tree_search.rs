@@ -54,6 +54,11 @@ where
 
     /// Choose a random action. Heuristics can be used to improved simulations
     pub fn choose(&self, actions: Vec<A>) -> A {
+        self.pick(&actions)
+    }
+
+    /// Choose a random action out of a list that is reused (e.g. by the simulations)
+    fn pick(&self, actions: &[A]) -> A {
         if actions.len() == 1 {
             return actions[0];
         }
@@ -70,9 +75,11 @@ where
         let mut local_stats = self.players.iter().map(|p| (p, 0f64)).collect::<Vec<_>>();
 
         let loss_penalty = self.level.loss_penalty();
+        let mut actions = vec![];
 
         while !current_node.as_ref().borrow().is_terminal() {
-            let action = self.choose(current_node.borrow().get_actions());
+            current_node.borrow().fill_actions(&mut actions);
+            let action = self.pick(&actions);
 
             let (next_state, next_player) = current_node.as_ref().borrow().execute(&action);
 
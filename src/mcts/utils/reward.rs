@@ -1,6 +1,17 @@
+/// The rule that ended a game in a draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawRule {
+    /// Too many moves were played without any progress (see `RuleSet::quiet_mvs`)
+    QuietMoves,
+    /// The same position was reached three times
+    Repetition,
+    /// A lone king held out against the opponent's kings for too long (see `RuleSet::endgame_mvs`)
+    Endgame,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Reward<P> {
-    Draw,
+    Draw(DrawRule),
     Continue,
     WonBy(P),
 }